@@ -0,0 +1,265 @@
+//! Derive macros for photon.
+//!
+//! `#[derive(Interpolatable)]` interpolates a struct field by field, each field through its own
+//! `Interpolatable` impl. Fields can be adjusted with the `interpolatable` attribute:
+//!
+//! - `#[interpolatable(skip)]` keeps the origin's value, which must be `Clone`
+//! - `#[interpolatable(ease = "path::to::function")]` eases this field with its own function
+//!   instead of the one passed to `interpolate`
+//! - `#[interpolatable(space = "logarithmic")]` blends this field in its own space, `"linear"` or
+//!   `"logarithmic"`, whatever space the caller asks for, and `#[interpolatable(power_law = 0.5)]`
+//!   in a power-law space with that exponent
+//! - `#[interpolatable(offset)]` adds an `<field>_offset` entry to the generated asynchronous
+//!   options, passed on as the field's own asynchronous options
+//!
+//! The asynchronous options struct is named `<Struct>AsynchronousOptions` unless the struct is
+//! annotated with `#[interpolatable(options = "Name")]`. Structs with no offset fields use `()`.
+//! The options struct is `Clone`, so derived structs can be interpolated inside collections, and
+//! `Default`, with every offset unset.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, ExprPath, Fields, Ident, Lit, Meta,
+    NestedMeta,
+};
+
+#[proc_macro_derive(Interpolatable, attributes(interpolatable))]
+pub fn derive_interpolatable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    skip: bool,
+    offset: bool,
+    ease: Option<ExprPath>,
+    space: Option<TokenStream2>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let visibility = &input.vis;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "Interpolatable can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "Interpolatable can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut options_name = format_ident!("{}AsynchronousOptions", name);
+    for meta in interpolatable_metas(&input.attrs)? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("options") => {
+                options_name = Ident::new(&string_value(&pair.lit)?, pair.lit.span());
+            }
+            other => return Err(Error::new(other.span(), "unknown interpolatable option")),
+        }
+    }
+
+    let mut offsets = vec![];
+    let mut initialisers = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let options = field_options(&field.attrs)?;
+
+        if options.skip {
+            initialisers.push(quote! { #ident: ::core::clone::Clone::clone(&self.#ident) });
+            continue;
+        }
+
+        let ease = match &options.ease {
+            Some(function) => quote! {
+                ::core::option::Option::Some(#function as crate::transition::transition::EasingFunction)
+            },
+            None => quote! { ease },
+        };
+        let space = options.space.unwrap_or_else(|| quote! { space });
+        let asynchronous = if options.offset {
+            let offset = format_ident!("{}_offset", ident);
+            offsets.push((offset.clone(), ty));
+            quote! { #offset }
+        } else {
+            quote! { ::core::option::Option::None }
+        };
+        initialisers.push(quote! {
+            #ident: <#ty as crate::transition::transition::Interpolatable>::interpolate_in_space(
+                &self.#ident,
+                &other.#ident,
+                transition,
+                #ease,
+                #space,
+                #asynchronous,
+            )
+        });
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    if !offsets.is_empty() && !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "offset fields are not supported on generic structs",
+        ));
+    }
+
+    let (options_type, options_definition, unpack) = if offsets.is_empty() {
+        (
+            quote! { () },
+            quote! {},
+            quote! { let _ = asynchronous_options; },
+        )
+    } else {
+        let names: Vec<&Ident> = offsets.iter().map(|(offset, _)| offset).collect();
+        let nones = names
+            .iter()
+            .map(|_| quote! { ::core::option::Option::None });
+        let definitions = offsets.iter().map(|(offset, ty)| {
+            quote! {
+                pub #offset: ::core::option::Option<
+                    <#ty as crate::transition::transition::Interpolatable>::AO
+                >
+            }
+        });
+        let documentation = format!(
+            "Per-field asynchronous options for interpolating a `{}`.",
+            name
+        );
+        (
+            quote! { #options_name },
+            quote! {
+                #[doc = #documentation]
+                #[derive(Clone, Default)]
+                #visibility struct #options_name {
+                    #(#definitions,)*
+                }
+            },
+            quote! {
+                let (#(#names,)*) = match asynchronous_options {
+                    ::core::option::Option::Some(options) => (#(options.#names,)*),
+                    ::core::option::Option::None => (#(#nones,)*),
+                };
+            },
+        )
+    };
+
+    Ok(quote! {
+        #options_definition
+
+        impl #impl_generics crate::transition::transition::Interpolatable for #name #type_generics #where_clause {
+            type AO = #options_type;
+
+            fn interpolate(
+                &self,
+                other: &Self,
+                transition: f64,
+                ease: ::core::option::Option<crate::transition::transition::EasingFunction>,
+                asynchronous_options: ::core::option::Option<Self::AO>,
+            ) -> Self {
+                self.interpolate_in_space(
+                    other,
+                    transition,
+                    ease,
+                    crate::transition::transition::InterpolationSpace::Linear,
+                    asynchronous_options,
+                )
+            }
+
+            fn interpolate_in_space(
+                &self,
+                other: &Self,
+                transition: f64,
+                ease: ::core::option::Option<crate::transition::transition::EasingFunction>,
+                space: crate::transition::transition::InterpolationSpace,
+                asynchronous_options: ::core::option::Option<Self::AO>,
+            ) -> Self {
+                #unpack
+                #name {
+                    #(#initialisers,)*
+                }
+            }
+        }
+    })
+}
+
+fn interpolatable_metas(attributes: &[Attribute]) -> Result<Vec<NestedMeta>, Error> {
+    let mut metas = vec![];
+    for attribute in attributes
+        .iter()
+        .filter(|attribute| attribute.path.is_ident("interpolatable"))
+    {
+        match attribute.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            other => return Err(Error::new(other.span(), "expected #[interpolatable(...)]")),
+        }
+    }
+    Ok(metas)
+}
+
+fn field_options(attributes: &[Attribute]) -> Result<FieldOptions, Error> {
+    let mut options = FieldOptions::default();
+    for meta in interpolatable_metas(attributes)? {
+        match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => options.skip = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("offset") => options.offset = true,
+            NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("ease") => {
+                options.ease = Some(syn::parse_str(&string_value(&pair.lit)?)?);
+            }
+            NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("space") => {
+                let space = match string_value(&pair.lit)?.as_str() {
+                    "linear" => quote! { Linear },
+                    "logarithmic" => quote! { Logarithmic },
+                    _ => {
+                        return Err(Error::new(
+                            pair.lit.span(),
+                            "expected \"linear\" or \"logarithmic\"",
+                        ))
+                    }
+                };
+                options.space =
+                    Some(quote! { crate::transition::transition::InterpolationSpace::#space });
+            }
+            NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("power_law") => {
+                let exponent = match &pair.lit {
+                    Lit::Float(float) => float.base10_parse::<f64>()?,
+                    Lit::Int(int) => int.base10_parse::<f64>()?,
+                    other => return Err(Error::new(other.span(), "expected an exponent")),
+                };
+                options.space = Some(quote! {
+                    crate::transition::transition::InterpolationSpace::PowerLaw(#exponent)
+                });
+            }
+            other => return Err(Error::new(other.span(), "unknown interpolatable option")),
+        }
+    }
+    if options.skip && (options.offset || options.ease.is_some() || options.space.is_some()) {
+        return Err(Error::new(
+            proc_macro2::Span::call_site(),
+            "a skipped field cannot also be eased, offset or given a space",
+        ));
+    }
+    Ok(options)
+}
+
+fn string_value(literal: &Lit) -> Result<String, Error> {
+    match literal {
+        Lit::Str(string) => Ok(string.value()),
+        other => Err(Error::new(other.span(), "expected a string")),
+    }
+}
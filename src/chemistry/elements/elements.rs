@@ -77,7 +77,7 @@ impl Element {
        }
     }
 
-    pub fn symbol(&self) -> &str {
+    pub fn symbol(&self) -> &'static str {
         match self {
             Element::Hydrogen(_) => "H",
             Element::Helium(_) => "He",
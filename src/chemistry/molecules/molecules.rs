@@ -20,11 +20,12 @@ pub struct Molecule(Vec<MolecularComponent>);
 
 impl PartialEq for Molecule {
     fn eq(&self, other: &Self) -> bool {
-        self.0.iter().zip(other.0.iter()).all(
-            |((component_a_el, component_a_ratio), (component_b_el, component_b_ratio))| {
-                component_a_el == component_b_el && component_a_ratio == component_b_ratio
-            },
-        )
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(other.0.iter()).all(
+                |((component_a_el, component_a_ratio), (component_b_el, component_b_ratio))| {
+                    component_a_el == component_b_el && component_a_ratio == component_b_ratio
+                },
+            )
     }
 }
 impl Eq for Molecule {}
@@ -79,6 +80,40 @@ impl Molecule {
         Molecule::new(vec![(Element::Helium(HeliumIsotope::Helium), 1)])
     }
 
+    /// The elemental isotopes that make up this molecule and how many of each it contains
+    pub fn components(&self) -> &[MolecularComponent] {
+        &self.0
+    }
+
+    /// Whether this species is refractory and condenses into interstellar dust grains.
+    /// Anything containing silicon, and pure carbon, is treated as dust.
+    pub fn is_dust(&self) -> bool {
+        self.0
+            .iter()
+            .any(|(element, _)| matches!(element, Element::Silicon(_)))
+            || !self.0.is_empty()
+                && self
+                    .0
+                    .iter()
+                    .all(|(element, _)| matches!(element, Element::Carbon(_)))
+    }
+
+    /// The number of atoms in one molecule
+    pub fn atom_count(&self) -> usize {
+        self.0.iter().map(|(_, count)| count).sum()
+    }
+
+    /// The degrees of freedom a molecule can store heat in at cloud temperatures: translation
+    /// only for single atoms, plus rotation about two axes for pairs and three for anything
+    /// larger. Vibration is frozen out, and every larger molecule is treated as non-linear.
+    pub fn degrees_of_freedom(&self) -> f64 {
+        match self.atom_count() {
+            0 | 1 => 3.0,
+            2 => 5.0,
+            _ => 6.0,
+        }
+    }
+
     pub fn relative_formula_mass(&self) -> f64 {
         self.0.iter().fold(0.0, |acc, (element, count)| {
             acc + (element.data().atomic_mass_number() * *count as f64)
@@ -42,4 +42,21 @@ mod tests {
         let mass_in_2_moles = carbon_dioxide.mass_in_amount(AmountOfSubstance::new::<mole>(2.0));
         assert_eq!(mass_in_2_moles, Mass::new::<gram>(87.9796));
     }
+
+    #[test]
+    fn dust_test() {
+        assert!(Molecule::new(vec![(Element::Carbon(CarbonIsotope::Carbon), 1)]).is_dust());
+        assert!(!Molecule::carbon_monoxide().is_dust());
+        assert!(!Molecule::molecular_hydrogen().is_dust());
+        assert!(!Molecule::new(vec![]).is_dust());
+        assert_eq!(Molecule::water().components().len(), 2);
+    }
+
+    #[test]
+    fn degrees_of_freedom_test() {
+        assert_eq!(Molecule::atomic_helium().degrees_of_freedom(), 3.0);
+        assert_eq!(Molecule::molecular_hydrogen().degrees_of_freedom(), 5.0);
+        assert_eq!(Molecule::water().atom_count(), 3);
+        assert_eq!(Molecule::water().degrees_of_freedom(), 6.0);
+    }
 }
@@ -1,18 +1,21 @@
+use std::sync::OnceLock;
+
 use rand::Rng;
-use rand_chacha::ChaCha20Rng;
-use rand_seeder::rand_core::SeedableRng;
-use uom::fmt::DisplayStyle::Abbreviation;
-use uom::si::amount_of_substance::mole;
-use uom::si::f64::{AmountOfSubstance, Length, Mass, MassDensity, MolarMass, Pressure, ThermodynamicTemperature, Volume};
-use uom::si::length::{astronomical_unit, light_year};
+use uom::si::f64::{Length, Mass, MassDensity, Pressure, ThermodynamicTemperature, Volume};
+use uom::si::length::light_year;
 use uom::si::mass::kilogram;
-use uom::si::power::watt;
 use uom::si::pressure::pascal;
-use uom::si::volume::cubic_meter;
-use crate::{Cartesian, Coordinates, solar_mass};
+use crate::{Cartesian, Coordinates};
 use crate::chemistry::molecules::molecules::Molecule;
+use crate::coordinates::position::{LocalPosition, SectorPosition};
+use crate::cloud::density_field::{DensityField, DensityFieldOptions, DEFAULT_RESOLUTION};
 use crate::formulae::formulae::{length, mass, volume};
-use crate::units::units::volume::cubic_lightyear;
+use crate::gas::composition::Composition;
+use crate::gas::fragmentation::{self, Fragment, FragmentationOptions};
+use crate::gas::gas::UniformGas;
+
+/// How many times denser than the cloud's mean a peak must be to be considered a core.
+const CORE_OVERDENSITY: f64 = 2.0;
 
 impl Cartesian for MolecularCloud {
     fn coordinates(&self) -> &Coordinates {
@@ -24,6 +27,8 @@ pub struct CloudOptions {
     pub use_randomness: bool,
     pub core_formation_chance: f64,
     pub core_formation_dropoff: f64,
+    /// Turbulence that structures the cloud's density. Without it the cloud is uniform.
+    pub turbulence: Option<DensityFieldOptions>,
 }
 
 #[derive(Debug)]
@@ -37,6 +42,12 @@ pub struct MolecularCloud {
     pub mass: Mass,
 
     pub contents: Vec<(Molecule, f64)>,
+
+    /// How many cores have condensed in the cloud
+    pub core_count: usize,
+
+    turbulence: Option<DensityFieldOptions>,
+    density_field: OnceLock<DensityField>,
 }
 
 impl MolecularCloud {
@@ -44,14 +55,68 @@ impl MolecularCloud {
         Pressure::new::<pascal>(1.322e-11)
     }
 
+    /// The structure of the cloud's density. A turbulent field is costly to build, so it is
+    /// only generated the first time it is needed.
+    pub fn density_field(&self) -> &DensityField {
+        self.density_field.get_or_init(|| match &self.turbulence {
+            Some(turbulence) => {
+                let mut rng = self.coordinates.seed().derive("cloud").derive("density_field").rng();
+                DensityField::new(&mut rng, self.radius, self.mass, turbulence)
+            }
+            None => DensityField::uniform(self.radius, self.mass),
+        })
+    }
+
+    /// The density of the cloud at an offset from its centre
+    pub fn density_at(&self, x: Length, y: Length, z: Length) -> MassDensity {
+        self.density_field().density_at(x, y, z)
+    }
+
+    /// Where cores are most likely to condense, the densest peaks of the cloud's density field,
+    /// densest first, relative to the centre of the cloud. A uniform cloud has no peaks.
+    pub fn core_positions(&self, count: usize) -> Vec<LocalPosition> {
+        let centre = SectorPosition::centre(self.coordinates);
+        self.density_field()
+            .peaks(DEFAULT_RESOLUTION, CORE_OVERDENSITY)
+            .into_iter()
+            .take(count)
+            .map(|(position, _)| LocalPosition::new(centre, position))
+            .collect()
+    }
+
+    /// The mean mass of a single particle of the cloud, weighted by the ratio of each material
+    pub fn mean_particle_mass(&self) -> Mass {
+        self.composition().mean_molecular_mass()
+    }
+
+    /// The fraction of the cloud's mass that is locked up in dust grains
+    pub fn dust_mass_fraction(&self) -> f64 {
+        self.composition().dust_mass_fraction()
+    }
+
+    /// Break the cloud into fragments of around a Jeans mass at `temperature`, with offsets from
+    /// the centre of the cloud. Returns `None` if the cloud has no contents.
+    pub fn fragment(&self, temperature: ThermodynamicTemperature, options: &FragmentationOptions) -> Option<Vec<Fragment>> {
+        let materials = self.composition().normalised()?;
+        let molecules_per_cubic_meter = (self.mass / self.volume / materials.mean_molecular_mass()).value;
+        let gas = UniformGas::composite_from_vacuum_properties(self.volume, molecules_per_cubic_meter, temperature, materials);
+        let seed = self.coordinates.seed().derive("cloud").derive("fragments");
+        Some(fragmentation::fragment(&gas, seed, options))
+    }
+
+    fn composition(&self) -> Composition {
+        Composition(self.contents.clone())
+    }
+
     pub fn new(coordinates: Coordinates, radius: Length, average_density: MassDensity, contents: Vec<(Molecule, f64)>, options: CloudOptions) -> MolecularCloud {
-        let mut rng = ChaCha20Rng::seed_from_u64(coordinates.hash);
+        // each part of the cloud draws from its own stream, so changing one leaves the rest alone
+        let seed = coordinates.seed().derive("cloud");
 
         let (volume, actual_radius) = {
             if !options.use_randomness {
                 (volume::sphere_volume_from_length(radius), radius)
             } else {
-                let modifier = rng.gen_range(0.5..1.5);
+                let modifier = seed.derive("radius").rng().gen_range(0.5..1.5);
                 let volume = volume::sphere_volume_from_length(radius * modifier);
                 (volume, length::sphere_radius_from_volume(volume))
             }
@@ -60,23 +125,19 @@ impl MolecularCloud {
         /// The mass of the diffuse part of the molecular cloud.
         // get the diffuse mass level by getting the masses of each constituent material and summing them.
         let diffuse_mass = contents.iter().map(|(material, ratio)| {
-            println!();
             let volume_of_material: Volume = volume * (*ratio / 100.0);
             mass::from_volume_and_density(volume_of_material, average_density)
         }).fold(Mass::new::<kilogram>(0.0), |acc, mass| acc + mass);
 
-        println!("diffuse mass {:?}", diffuse_mass.into_format_args(solar_mass, Abbreviation));
-
         // use the diameter to determine number of available cores.
         // for example a cloud 1 lightyear across can have a maximum of 1 possible core.
         // A cloud 50 light years across has 200 possible cores
         let number_of_possible_cores = (actual_radius.value * 2.0 / Length::new::<light_year>(1.0).value) * 4.0;
-        println!();
-        println!("possible cores {}", number_of_possible_cores);
 
         // the number of actual cores in this cloud
+        let mut rng = seed.derive("cores").rng();
         let mut chance = options.core_formation_chance;
-        let possible_cores = (0..(number_of_possible_cores as usize)).fold(0, |acc, _| {
+        let core_count = (0..(number_of_possible_cores as usize)).fold(0, |acc, _| {
             let roll = rng.gen_range(0.0..100.0);
             if roll <= chance {
                 chance *= options.core_formation_dropoff;
@@ -86,8 +147,6 @@ impl MolecularCloud {
             }
         });
 
-        println!("cores: {}", possible_cores);
-
         // let core_density: MassDensity = {
         //     let value = 10e8;
         //     // let value = rng.gen_range(10e4..10e6);
@@ -98,14 +157,16 @@ impl MolecularCloud {
         // println!("core density: {:?}", core_density.into_format_args(hydrogen_atom_per_cubic_centimeter, Abbreviation));
         // println!("average core mass {:?}", mass::from_volume_and_density(Volume::new::<cubic_lightyear>(1.0), core_density).into_format_args(solar_mass, Abbreviation));
 
-        println!();
         MolecularCloud {
             radius: actual_radius,
             volume,
             coordinates,
             average_density,
             mass: diffuse_mass,
-            contents
+            contents,
+            core_count,
+            turbulence: options.turbulence,
+            density_field: OnceLock::new(),
         }
     }
 }
@@ -56,6 +56,7 @@ mod tests {
                 use_randomness: false,
                 core_formation_chance: 80.0,
                 core_formation_dropoff: 0.80,
+                turbulence: None,
             },
         );
 
@@ -0,0 +1,233 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use uom::si::f64::{Length, Mass, MassDensity};
+use uom::si::length::meter;
+use uom::si::mass::kilogram;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+
+use crate::coordinates::position::Position;
+use crate::formulae::formulae::{density, volume};
+
+/// The number of cells along each side of the grid used to normalise the field and search it
+/// for peaks.
+pub const DEFAULT_RESOLUTION: usize = 32;
+
+/// The smallest structure resolved by the field, as a fraction of the cloud's diameter.
+const SMALLEST_SCALE: f64 = 1.0 / 64.0;
+
+#[derive(Debug, Clone)]
+pub struct DensityFieldOptions {
+    /// The sonic Mach number of the turbulence. Faster turbulence widens the density PDF.
+    pub mach_number: f64,
+    /// How compressive the turbulent driving is, from 1/3 for purely solenoidal driving to 1 for
+    /// purely compressive driving.
+    pub forcing: f64,
+    /// The slope of the 3D power spectrum of log density, `P(k) ∝ k^-n`. 11/3 is Kolmogorov,
+    /// 4 is Burgers turbulence.
+    pub spectral_index: f64,
+    /// The number of Fourier modes summed to build the field
+    pub modes: usize,
+}
+
+/// A single plane wave of the turbulent field.
+#[derive(Debug, Clone)]
+struct Mode {
+    wavevector: [f64; 3],
+    amplitude: f64,
+    phase: f64,
+}
+
+/// A seeded turbulent density field filling a spherical cloud.
+///
+/// The log of the density is a Gaussian random field with a power-law spectrum, built from a sum
+/// of randomly oriented plane waves, so the density follows the log-normal PDF seen in
+/// simulations of supersonic turbulence with width `σ² = ln(1 + b²M²)`. The field is scaled so
+/// that it integrates to the cloud's mass.
+#[derive(Debug, Clone)]
+pub struct DensityField {
+    pub radius: Length,
+    pub mass: Mass,
+    /// The standard deviation of the natural log of the density
+    pub sigma: f64,
+    modes: Vec<Mode>,
+    normalisation: f64,
+}
+
+impl DensityField {
+    /// A field with no structure, the same density everywhere inside the cloud.
+    pub fn uniform(radius: Length, mass: Mass) -> DensityField {
+        let mean_density =
+            density::from_mass_and_volume(mass, volume::sphere_volume_from_length(radius));
+        DensityField {
+            radius,
+            mass,
+            sigma: 0.0,
+            modes: vec![],
+            normalisation: mean_density.value,
+        }
+    }
+
+    /// Generate a turbulent field for a cloud. The same rng state always produces the same field.
+    pub fn new<R: Rng>(
+        rng: &mut R,
+        radius: Length,
+        mass: Mass,
+        options: &DensityFieldOptions,
+    ) -> DensityField {
+        let diameter = 2.0 * radius.value;
+        let smallest_wavenumber = 2.0 * PI / diameter;
+        let largest_wavenumber = 2.0 * PI / (diameter * SMALLEST_SCALE);
+
+        let mut modes: Vec<Mode> = (0..options.modes)
+            .map(|_| {
+                // wavenumbers are spread evenly in log k, so each mode carries the power of a
+                // logarithmic shell, k³ P(k)
+                let fraction: f64 = rng.gen_range(0.0..1.0);
+                let wavenumber =
+                    smallest_wavenumber * (largest_wavenumber / smallest_wavenumber).powf(fraction);
+                let cos_theta: f64 = rng.gen_range(-1.0..1.0);
+                let azimuth: f64 = rng.gen_range(0.0..2.0 * PI);
+                let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+                Mode {
+                    wavevector: [
+                        wavenumber * sin_theta * azimuth.cos(),
+                        wavenumber * sin_theta * azimuth.sin(),
+                        wavenumber * cos_theta,
+                    ],
+                    amplitude: wavenumber.powf((3.0 - options.spectral_index) / 2.0),
+                    phase: rng.gen_range(0.0..2.0 * PI),
+                }
+            })
+            .collect();
+
+        // scale the modes so the Gaussian field has unit variance
+        let variance: f64 = modes.iter().map(|mode| mode.amplitude.powi(2) / 2.0).sum();
+        if variance > 0.0 {
+            modes
+                .iter_mut()
+                .for_each(|mode| mode.amplitude /= variance.sqrt());
+        }
+
+        let sigma = (1.0 + (options.forcing * options.mach_number).powi(2))
+            .ln()
+            .sqrt();
+        let mut field = DensityField {
+            radius,
+            mass,
+            sigma,
+            modes,
+            normalisation: 1.0,
+        };
+
+        // normalise against the same grid used to integrate the field, so the integral is exact
+        let unnormalised = field.integrate(DEFAULT_RESOLUTION).value;
+        field.normalisation = if unnormalised > 0.0 {
+            mass.value / unnormalised
+        } else {
+            0.0
+        };
+        field
+    }
+
+    /// The Gaussian random field underlying the log density, with zero mean and unit variance
+    fn gaussian_at(&self, position: [f64; 3]) -> f64 {
+        self.modes
+            .iter()
+            .map(|mode| {
+                let phase = mode.wavevector[0] * position[0]
+                    + mode.wavevector[1] * position[1]
+                    + mode.wavevector[2] * position[2]
+                    + mode.phase;
+                mode.amplitude * phase.cos()
+            })
+            .sum()
+    }
+
+    /// The density at an offset from the centre of the cloud. Zero outside the cloud.
+    pub fn density_at(&self, x: Length, y: Length, z: Length) -> MassDensity {
+        let position = [x.value, y.value, z.value];
+        if position.iter().map(|p| p * p).sum::<f64>() > self.radius.value.powi(2) {
+            return MassDensity::new::<kilogram_per_cubic_meter>(0.0);
+        }
+        // the -σ²/2 offset keeps the mean of the log-normal at one
+        let contrast = (self.sigma * self.gaussian_at(position) - self.sigma.powi(2) / 2.0).exp();
+        MassDensity::new::<kilogram_per_cubic_meter>(self.normalisation * contrast)
+    }
+
+    /// The mean density of the cloud
+    pub fn mean_density(&self) -> MassDensity {
+        density::from_mass_and_volume(self.mass, volume::sphere_volume_from_length(self.radius))
+    }
+
+    /// The centres of a cubic grid of cells spanning the cloud, with the width of each cell
+    fn cells(&self, resolution: usize) -> (Vec<[f64; 3]>, f64) {
+        let cell = 2.0 * self.radius.value / resolution as f64;
+        let centre = |index: usize| -self.radius.value + (index as f64 + 0.5) * cell;
+        let mut cells = Vec::with_capacity(resolution.pow(3));
+        for i in 0..resolution {
+            for j in 0..resolution {
+                for k in 0..resolution {
+                    cells.push([centre(i), centre(j), centre(k)]);
+                }
+            }
+        }
+        (cells, cell)
+    }
+
+    fn density_at_position(&self, position: [f64; 3]) -> MassDensity {
+        self.density_at(
+            Length::new::<meter>(position[0]),
+            Length::new::<meter>(position[1]),
+            Length::new::<meter>(position[2]),
+        )
+    }
+
+    /// The mass of the field, summed over a grid of `resolution`³ cells
+    pub fn integrate(&self, resolution: usize) -> Mass {
+        let (cells, cell) = self.cells(resolution);
+        Mass::new::<kilogram>(
+            cells
+                .into_iter()
+                .map(|position| self.density_at_position(position).value * cell.powi(3))
+                .sum(),
+        )
+    }
+
+    /// Local density maxima on a grid of `resolution`³ cells that are at least `overdensity`
+    /// times the mean density, densest first. These are where cores are most likely to form.
+    pub fn peaks(&self, resolution: usize, overdensity: f64) -> Vec<(Position, MassDensity)> {
+        let (cells, _) = self.cells(resolution);
+        let densities: Vec<f64> = cells
+            .iter()
+            .map(|position| self.density_at_position(*position).value)
+            .collect();
+        let threshold = self.mean_density().value * overdensity;
+        let index = |i: usize, j: usize, k: usize| (i * resolution + j) * resolution + k;
+
+        let mut peaks = vec![];
+        for i in 1..resolution.saturating_sub(1) {
+            for j in 1..resolution.saturating_sub(1) {
+                for k in 1..resolution.saturating_sub(1) {
+                    let value = densities[index(i, j, k)];
+                    if value < threshold {
+                        continue;
+                    }
+                    let is_peak = (0..27).filter(|n| *n != 13).all(|n| {
+                        densities[index(i + n / 9 - 1, j + (n / 3) % 3 - 1, k + n % 3 - 1)] < value
+                    });
+                    if is_peak {
+                        let position =
+                            Position::from(cells[index(i, j, k)].map(Length::new::<meter>));
+                        peaks.push((
+                            position,
+                            MassDensity::new::<kilogram_per_cubic_meter>(value),
+                        ));
+                    }
+                }
+            }
+        }
+        peaks.sort_by(|a, b| b.1.value.total_cmp(&a.1.value));
+        peaks
+    }
+}
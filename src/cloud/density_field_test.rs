@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    use crate::chemistry::molecules::molecules::Molecule;
+    use crate::cloud::cloud::{CloudOptions, MolecularCloud};
+    use crate::cloud::density_field::{DensityField, DensityFieldOptions, DEFAULT_RESOLUTION};
+    use crate::gas::gas::UniformGas;
+    use crate::units::units::mass::solar_mass;
+    use crate::Coordinates;
+    use float_cmp::assert_approx_eq;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+    use uom::si::f64::{Length, Mass};
+    use uom::si::length::{light_year, meter};
+
+    fn turbulence() -> DensityFieldOptions {
+        DensityFieldOptions {
+            mach_number: 10.0,
+            forcing: 0.4,
+            spectral_index: 11.0 / 3.0,
+            modes: 64,
+        }
+    }
+
+    fn field(seed: u64) -> DensityField {
+        DensityField::new(
+            &mut ChaCha20Rng::seed_from_u64(seed),
+            Length::new::<light_year>(10.0),
+            Mass::new::<solar_mass>(1000.0),
+            &turbulence(),
+        )
+    }
+
+    fn log_contrasts(field: &DensityField, samples: usize) -> Vec<f64> {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let radius = field.radius.value;
+        let mean = field.mean_density().value;
+        (0..samples)
+            .map(|_| {
+                // sample well inside the cloud so the edge does not matter
+                let mut point = || Length::new::<meter>(rng.gen_range(-0.5..0.5) * radius);
+                (field.density_at(point(), point(), point()).value / mean).ln()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn density_field_integrates_to_mass() {
+        let uniform = DensityField::uniform(
+            Length::new::<light_year>(10.0),
+            Mass::new::<solar_mass>(1000.0),
+        );
+        assert_approx_eq!(
+            f64,
+            uniform.integrate(64).get::<solar_mass>(),
+            1000.0,
+            epsilon = 10.0
+        );
+
+        let turbulent = field(1);
+        assert_approx_eq!(
+            f64,
+            turbulent.integrate(DEFAULT_RESOLUTION).get::<solar_mass>(),
+            1000.0,
+            epsilon = 1e-6
+        );
+        assert_approx_eq!(
+            f64,
+            turbulent.integrate(48).get::<solar_mass>(),
+            1000.0,
+            epsilon = 50.0
+        );
+    }
+
+    #[test]
+    fn density_field_is_log_normal() {
+        let field = field(2);
+        // σ² = ln(1 + b²M²) for b = 0.4 and M = 10
+        assert_approx_eq!(f64, field.sigma, 17.0_f64.ln().sqrt(), epsilon = 1e-9);
+
+        let contrasts = log_contrasts(&field, 4000);
+        let mean = contrasts.iter().sum::<f64>() / contrasts.len() as f64;
+        let deviation = (contrasts.iter().map(|c| (c - mean).powi(2)).sum::<f64>()
+            / contrasts.len() as f64)
+            .sqrt();
+        assert_approx_eq!(f64, deviation / field.sigma, 1.0, epsilon = 0.25);
+
+        // log-normal, so the log density should be symmetric about its mean
+        let skew = contrasts
+            .iter()
+            .map(|c| ((c - mean) / deviation).powi(3))
+            .sum::<f64>()
+            / contrasts.len() as f64;
+        assert!(skew.abs() < 0.5);
+    }
+
+    #[test]
+    fn density_field_is_correlated_on_small_scales() {
+        // a red power spectrum means nearby points have similar densities
+        let field = field(3);
+        let structure = |separation: f64| {
+            let mut rng = ChaCha20Rng::seed_from_u64(4);
+            let radius = field.radius.value;
+            (0..2000)
+                .map(|_| {
+                    let x = rng.gen_range(-0.4..0.4) * radius;
+                    let y = rng.gen_range(-0.4..0.4) * radius;
+                    let z = rng.gen_range(-0.4..0.4) * radius;
+                    let at = |offset: f64| {
+                        field
+                            .density_at(
+                                Length::new::<meter>(x + offset),
+                                Length::new::<meter>(y),
+                                Length::new::<meter>(z),
+                            )
+                            .value
+                            .ln()
+                    };
+                    (at(separation * radius) - at(0.0)).powi(2)
+                })
+                .sum::<f64>()
+        };
+        assert!(structure(0.01) < structure(0.1));
+        assert!(structure(0.1) < structure(0.5));
+    }
+
+    #[test]
+    fn density_field_is_deterministic() {
+        let origin = Length::new::<light_year>(1.0);
+        assert_eq!(
+            field(5).density_at(origin, origin, origin),
+            field(5).density_at(origin, origin, origin)
+        );
+        assert_ne!(
+            field(5).density_at(origin, origin, origin),
+            field(6).density_at(origin, origin, origin)
+        );
+        assert_eq!(
+            field(5)
+                .density_at(Length::new::<light_year>(11.0), origin, origin)
+                .value,
+            0.0
+        );
+    }
+
+    #[test]
+    fn density_field_peaks() {
+        let field = field(7);
+        let peaks = field.peaks(DEFAULT_RESOLUTION, 2.0);
+        assert!(!peaks.is_empty());
+        assert!(peaks.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(peaks
+            .iter()
+            .all(|(_, density)| *density >= field.mean_density() * 2.0));
+
+        let uniform = DensityField::uniform(field.radius, field.mass);
+        assert!(uniform.peaks(DEFAULT_RESOLUTION, 2.0).is_empty());
+    }
+
+    #[test]
+    fn cloud_cores_form_at_density_peaks() {
+        let cloud = |turbulence: Option<DensityFieldOptions>| {
+            MolecularCloud::new(
+                Coordinates::new(3, 1, 4),
+                Length::new::<light_year>(10.0),
+                UniformGas::generate_massdensity(&Molecule::molecular_hydrogen(), 1e9),
+                vec![(Molecule::molecular_hydrogen(), 100.0)],
+                CloudOptions {
+                    use_randomness: false,
+                    core_formation_chance: 80.0,
+                    core_formation_dropoff: 0.8,
+                    turbulence,
+                },
+            )
+        };
+
+        let turbulent = cloud(Some(turbulence()));
+        let cores = turbulent.core_positions(5);
+        assert!(!cores.is_empty() && cores.len() <= 5);
+        assert!(cores
+            .iter()
+            .all(|core| core.offset.magnitude() < turbulent.radius));
+        assert_eq!(cores, cloud(Some(turbulence())).core_positions(5));
+
+        assert!(cloud(None).core_positions(5).is_empty());
+    }
+
+    #[test]
+    fn cloud_density_field_is_built_on_demand_from_its_own_stream() {
+        let coordinates = Coordinates::new(2, 7, 1);
+        let cloud = MolecularCloud::new(
+            coordinates,
+            Length::new::<light_year>(10.0),
+            UniformGas::generate_massdensity(&Molecule::molecular_hydrogen(), 1e9),
+            vec![(Molecule::molecular_hydrogen(), 100.0)],
+            CloudOptions {
+                use_randomness: false,
+                core_formation_chance: 80.0,
+                core_formation_dropoff: 0.8,
+                turbulence: Some(turbulence()),
+            },
+        );
+        let expected = DensityField::new(
+            &mut coordinates.seed().derive("cloud").derive("density_field").rng(),
+            cloud.radius,
+            cloud.mass,
+            &turbulence(),
+        );
+
+        let point = Length::new::<light_year>(2.0);
+        assert_eq!(
+            cloud.density_at(point, -point, point),
+            expected.density_at(point, -point, point)
+        );
+        assert_eq!(cloud.density_field().sigma, expected.sigma);
+    }
+}
@@ -0,0 +1,247 @@
+use rand::Rng;
+use uom::si::f64::{Length, Mass, ThermodynamicTemperature};
+use uom::si::length::{meter, micrometer};
+use uom::si::mass::kilogram;
+
+use crate::chemistry::elements::elements::Element;
+use crate::cloud::cloud::MolecularCloud;
+use crate::coordinates::coordinates::Cartesian;
+use crate::wavelength::spectrum::planck;
+
+/// Hydrogen column density that produces one magnitude of visual extinction in the diffuse ISM,
+/// `N_H / A_V = 1.87e21 cm⁻²` (Bohlin, Savage & Drake 1978), in m⁻².
+const HYDROGEN_COLUMN_PER_MAGNITUDE: f64 = 1.87e25;
+
+/// The dust-to-gas mass ratio the extinction calibration above was measured at.
+const REFERENCE_DUST_FRACTION: f64 = 0.01;
+
+/// Magnitudes of extinction per unit optical depth, `2.5 log₁₀ e`.
+const MAGNITUDES_PER_OPTICAL_DEPTH: f64 = 1.086;
+
+/// A rectangular grid of values, stored row by row from the top left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<f64>,
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize) -> Grid {
+        Grid {
+            width,
+            height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        self.values[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: f64) {
+        self.values[y * self.width + x] = value;
+    }
+
+    pub fn max(&self) -> f64 {
+        self.values.iter().cloned().fold(0.0, f64::max)
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.values.iter().sum()
+    }
+
+    /// A copy of the grid scaled so that its largest value is one
+    pub fn normalised(&self) -> Grid {
+        let max = self.max();
+        Grid {
+            width: self.width,
+            height: self.height,
+            values: self
+                .values
+                .iter()
+                .map(|value| if max > 0.0 { value / max } else { 0.0 })
+                .collect(),
+        }
+    }
+
+    /// Encode the grid as a binary 8-bit greyscale PGM (P5) image, normalised to its peak value.
+    /// Any image tool can convert this to PNG.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut bytes = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(
+            self.normalised()
+                .values
+                .iter()
+                .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8),
+        );
+        bytes
+    }
+
+    /// The raw values as little-endian 32-bit floats, ready to upload as a single channel texture
+    pub fn to_raw(&self) -> Vec<u8> {
+        self.values
+            .iter()
+            .flat_map(|value| (*value as f32).to_le_bytes())
+            .collect()
+    }
+}
+
+pub struct MapOptions {
+    /// The width and height of each map in pixels
+    pub resolution: usize,
+    /// The number of density samples taken along each line of sight
+    pub samples: usize,
+    /// The temperature of the dust grains, which sets the brightness of the thermal emission
+    pub dust_temperature: ThermodynamicTemperature,
+    /// The wavelength the emission map is observed at
+    pub emission_wavelength: Length,
+}
+
+/// Images of a molecular cloud projected along a line of sight. Each map covers the full
+/// diameter of the cloud, centred on the cloud.
+#[derive(Debug, Clone)]
+pub struct CloudMaps {
+    /// The physical width of a single pixel
+    pub pixel_size: Length,
+    /// Particles per square metre along each line of sight
+    pub column_density: Grid,
+    /// Visual extinction A_V in magnitudes
+    pub extinction: Grid,
+    /// Thermal dust emission in W·sr⁻¹·m⁻³ at the chosen wavelength
+    pub emission: Grid,
+}
+
+impl CloudMaps {
+    /// Project a cloud along `view_axis`, which need not be normalised.
+    ///
+    /// Each line of sight is integrated with evenly spaced samples, offset by a jitter drawn from
+    /// the cloud's coordinates so that the same cloud always renders identically without banding.
+    /// Dust is assumed to be optically thin with an opacity falling as λ⁻² from the V band.
+    ///
+    /// Returns `None` when the view axis has no length.
+    pub fn render(
+        cloud: &MolecularCloud,
+        view_axis: [f64; 3],
+        options: &MapOptions,
+    ) -> Option<CloudMaps> {
+        let (axis, horizontal, vertical) = basis(view_axis)?;
+        let mut rng = cloud.seed().derive("maps").rng();
+
+        let radius = cloud.radius.value;
+        let pixel_size = 2.0 * radius / options.resolution as f64;
+        let sample_length = 2.0 * radius / options.samples as f64;
+
+        let particle_mass = cloud.mean_particle_mass();
+        let hydrogen_per_kilogram = hydrogen_nuclei_per_kilogram(cloud);
+        let dust_scale = cloud.dust_mass_fraction() / REFERENCE_DUST_FRACTION;
+        let source_function = planck(options.emission_wavelength, options.dust_temperature);
+        let opacity_scale =
+            (Length::new::<micrometer>(0.55).value / options.emission_wavelength.value).powi(2);
+
+        let mut column_density = Grid::new(options.resolution, options.resolution);
+        let mut extinction = Grid::new(options.resolution, options.resolution);
+        let mut emission = Grid::new(options.resolution, options.resolution);
+
+        for y in 0..options.resolution {
+            for x in 0..options.resolution {
+                let u = -radius + (x as f64 + 0.5) * pixel_size;
+                // rows run from the top of the image downwards
+                let v = radius - (y as f64 + 0.5) * pixel_size;
+                let jitter: f64 = rng.gen_range(0.0..1.0);
+
+                let mass_column = (0..options.samples).fold(0.0, |acc, sample| {
+                    let w = -radius + (sample as f64 + jitter) * sample_length;
+                    let point = |i: usize| {
+                        Length::new::<meter>(u * horizontal[i] + v * vertical[i] + w * axis[i])
+                    };
+                    acc + cloud.density_at(point(0), point(1), point(2)).value * sample_length
+                });
+
+                let visual_extinction = mass_column * hydrogen_per_kilogram * dust_scale
+                    / HYDROGEN_COLUMN_PER_MAGNITUDE;
+                let optical_depth =
+                    visual_extinction / MAGNITUDES_PER_OPTICAL_DEPTH * opacity_scale;
+
+                column_density.set(x, y, mass_column / particle_mass.value);
+                extinction.set(x, y, visual_extinction);
+                emission.set(x, y, source_function * -(-optical_depth).exp_m1());
+            }
+        }
+
+        Some(CloudMaps {
+            pixel_size: Length::new::<meter>(pixel_size),
+            column_density,
+            extinction,
+            emission,
+        })
+    }
+
+    /// The mass of the cloud recovered by summing the column density map
+    pub fn projected_mass(&self, particle_mass: Mass) -> Mass {
+        Mass::new::<kilogram>(
+            self.column_density.sum() * self.pixel_size.value.powi(2) * particle_mass.value,
+        )
+    }
+}
+
+/// The number of hydrogen nuclei carried by each kilogram of the cloud's material
+fn hydrogen_nuclei_per_kilogram(cloud: &MolecularCloud) -> f64 {
+    let (hydrogen, mass) =
+        cloud
+            .contents
+            .iter()
+            .fold((0.0, 0.0), |(hydrogen, mass), (material, ratio)| {
+                let atoms: usize = material
+                    .components()
+                    .iter()
+                    .filter(|(element, _)| matches!(element, Element::Hydrogen(_)))
+                    .map(|(_, count)| count)
+                    .sum();
+                (
+                    hydrogen + atoms as f64 * ratio,
+                    mass + material.molecular_weight().value * ratio,
+                )
+            });
+    if mass > 0.0 {
+        hydrogen / mass
+    } else {
+        0.0
+    }
+}
+
+/// An orthonormal basis whose third vector points along the view axis
+fn basis(view_axis: [f64; 3]) -> Option<([f64; 3], [f64; 3], [f64; 3])> {
+    let length = dot(view_axis, view_axis).sqrt();
+    if length == 0.0 || !length.is_finite() {
+        return None;
+    }
+    let axis = view_axis.map(|component| component / length);
+
+    // pick whichever world axis is least aligned with the view to build the image plane from
+    let up = if axis[1].abs() < 0.9 {
+        [0.0, 1.0, 0.0]
+    } else {
+        [1.0, 0.0, 0.0]
+    };
+    let horizontal = normalise(cross(up, axis));
+    let vertical = cross(axis, horizontal);
+    Some((axis, horizontal, vertical))
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalise(a: [f64; 3]) -> [f64; 3] {
+    let length = dot(a, a).sqrt();
+    a.map(|component| component / length)
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use crate::chemistry::elements::carbon::CarbonIsotope;
+    use crate::chemistry::elements::elements::Element;
+    use crate::chemistry::elements::silicon::SiliconIsotope;
+    use crate::chemistry::molecules::molecules::Molecule;
+    use crate::cloud::cloud::{CloudOptions, MolecularCloud};
+    use crate::cloud::maps::{CloudMaps, Grid, MapOptions};
+    use crate::gas::gas::UniformGas;
+    use crate::Coordinates;
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, ThermodynamicTemperature};
+    use uom::si::length::{light_year, micrometer};
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    fn cloud(coordinates: Coordinates, with_dust: bool) -> MolecularCloud {
+        let mut contents = vec![
+            (Molecule::molecular_hydrogen(), 80.0),
+            (Molecule::carbon_monoxide(), 17.0),
+        ];
+        if with_dust {
+            contents.push((
+                Molecule::new(vec![(Element::Silicon(SiliconIsotope::Silicon), 1)]),
+                1.5,
+            ));
+            contents.push((
+                Molecule::new(vec![(Element::Carbon(CarbonIsotope::Carbon), 1)]),
+                1.5,
+            ));
+        }
+        MolecularCloud::new(
+            coordinates,
+            Length::new::<light_year>(10.0),
+            UniformGas::generate_massdensity(&Molecule::molecular_hydrogen(), 1e9),
+            contents,
+            CloudOptions {
+                use_randomness: false,
+                core_formation_chance: 80.0,
+                core_formation_dropoff: 0.8,
+                turbulence: None,
+            },
+        )
+    }
+
+    fn options() -> MapOptions {
+        MapOptions {
+            resolution: 32,
+            samples: 64,
+            dust_temperature: ThermodynamicTemperature::new::<kelvin>(15.0),
+            emission_wavelength: Length::new::<micrometer>(850.0),
+        }
+    }
+
+    #[test]
+    fn maps_conserve_mass() {
+        let cloud = cloud(Coordinates::new(0, 0, 0), true);
+        let maps = CloudMaps::render(&cloud, [0.0, 0.0, 1.0], &options()).unwrap();
+
+        let total = cloud.density_at(
+            Length::new::<light_year>(0.0),
+            Length::new::<light_year>(0.0),
+            Length::new::<light_year>(0.0),
+        ) * cloud.volume;
+        let projected = maps.projected_mass(cloud.mean_particle_mass());
+        assert_approx_eq!(f64, projected.value / total.value, 1.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn maps_peak_through_the_centre() {
+        let cloud = cloud(Coordinates::new(0, 0, 0), true);
+        let maps = CloudMaps::render(&cloud, [1.0, 2.0, -0.5], &options()).unwrap();
+
+        let centre = maps.extinction.get(16, 16);
+        assert!(centre > 0.0);
+        assert_eq!(maps.extinction.get(0, 0), 0.0);
+        assert!(maps.extinction.get(16, 2) < centre);
+        assert!(maps.emission.get(16, 2) < maps.emission.get(16, 16));
+    }
+
+    #[test]
+    fn maps_are_symmetric_for_a_sphere() {
+        let cloud = cloud(Coordinates::new(4, -2, 7), true);
+        let along_x = CloudMaps::render(&cloud, [1.0, 0.0, 0.0], &options()).unwrap();
+        let along_z = CloudMaps::render(&cloud, [0.0, 0.0, 3.0], &options()).unwrap();
+        assert_approx_eq!(
+            f64,
+            along_x.column_density.sum() / along_z.column_density.sum(),
+            1.0,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn maps_are_deterministic() {
+        let first = CloudMaps::render(
+            &cloud(Coordinates::new(1, 2, 3), true),
+            [1.0, 1.0, 1.0],
+            &options(),
+        )
+        .unwrap();
+        let second = CloudMaps::render(
+            &cloud(Coordinates::new(1, 2, 3), true),
+            [1.0, 1.0, 1.0],
+            &options(),
+        )
+        .unwrap();
+        assert_eq!(first.column_density, second.column_density);
+        assert_eq!(first.emission, second.emission);
+    }
+
+    #[test]
+    fn maps_without_dust_are_transparent() {
+        let cloud = cloud(Coordinates::new(0, 0, 0), false);
+        let maps = CloudMaps::render(&cloud, [0.0, 1.0, 0.0], &options()).unwrap();
+        assert!(maps.column_density.max() > 0.0);
+        assert_eq!(maps.extinction.max(), 0.0);
+        assert_eq!(maps.emission.max(), 0.0);
+    }
+
+    #[test]
+    fn maps_reject_empty_view_axis() {
+        let cloud = cloud(Coordinates::new(0, 0, 0), true);
+        assert!(CloudMaps::render(&cloud, [0.0, 0.0, 0.0], &options()).is_none());
+    }
+
+    #[test]
+    fn grid_encoding() {
+        let mut grid = Grid::new(2, 2);
+        grid.set(1, 0, 4.0);
+        grid.set(0, 1, 2.0);
+
+        let pgm = grid.to_pgm();
+        assert!(pgm.starts_with(b"P5\n2 2\n255\n"));
+        assert_eq!(&pgm[pgm.len() - 4..], &[0, 255, 128, 0]);
+
+        let raw = grid.to_raw();
+        assert_eq!(raw.len(), 16);
+        assert_eq!(&raw[4..8], &4.0_f32.to_le_bytes());
+    }
+}
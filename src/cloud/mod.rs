@@ -1,2 +1,6 @@
 pub mod cloud;
 mod cloud_test;
+pub mod density_field;
+mod density_field_test;
+pub mod maps;
+mod maps_test;
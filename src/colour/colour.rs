@@ -0,0 +1,218 @@
+use colortemp::RGB;
+use uom::si::f64::{Length, ThermodynamicTemperature};
+use uom::si::length::nanometer;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::wavelength::spectrum::planck;
+
+/// The range and resolution of wavelengths the eye is sensitive to, in nanometres
+const VISIBLE_FROM: f64 = 360.0;
+const VISIBLE_TO: f64 = 830.0;
+const VISIBLE_STEP: f64 = 1.0;
+
+/// The red end of the CIE 1931 spectral locus, where it settles beyond 700nm
+const REDDEST: Chromaticity = Chromaticity {
+    x: 0.7347,
+    y: 0.2653,
+};
+
+/// The temperature whose brightness every other brightness is relative to, i.e. the sun
+const REFERENCE_TEMPERATURE: f64 = 5778.0;
+
+/// The conversion from CIE XYZ to linear sRGB with a D65 white point
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.2406, -1.5372, -0.4986],
+    [-0.9689, 1.8758, 0.0415],
+    [0.0557, -0.2040, 1.0570],
+];
+
+/// A piecewise gaussian with different widths either side of its mean
+fn lobe(wavelength: f64, mean: f64, below: f64, above: f64) -> f64 {
+    let width = if wavelength < mean { below } else { above };
+    (-0.5 * ((wavelength - mean) / width).powi(2)).exp()
+}
+
+/// The CIE 1931 2° standard observer colour matching functions x̄, ȳ and z̄ at a given
+/// wavelength, using the multi-lobe fit of Wyman, Sloan & Shirley (2013).
+pub fn colour_matching(wavelength: Length) -> (f64, f64, f64) {
+    let nm = wavelength.get::<nanometer>();
+    (
+        1.056 * lobe(nm, 599.8, 37.9, 31.0) + 0.362 * lobe(nm, 442.0, 16.0, 26.7)
+            - 0.065 * lobe(nm, 501.1, 20.4, 26.2),
+        0.821 * lobe(nm, 568.8, 46.9, 40.5) + 0.286 * lobe(nm, 530.9, 16.3, 31.1),
+        1.217 * lobe(nm, 437.0, 11.8, 36.0) + 0.681 * lobe(nm, 459.0, 26.0, 13.8),
+    )
+}
+
+/// A colour in the CIE 1931 XYZ colour space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// The chromaticity coordinates of a colour, independent of its brightness
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chromaticity {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A colour with linear sRGB primaries, suitable for lighting calculations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearRgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+/// A gamma encoded sRGB colour with channels between 0 and 1, suitable for display
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Srgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl Xyz {
+    /// Integrate the Planck spectrum of a black body against the colour matching functions
+    pub fn from_temperature(temperature: ThermodynamicTemperature) -> Xyz {
+        let steps = ((VISIBLE_TO - VISIBLE_FROM) / VISIBLE_STEP) as usize;
+        (0..=steps).fold(
+            Xyz {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            |acc, step| {
+                let wavelength =
+                    Length::new::<nanometer>(VISIBLE_FROM + step as f64 * VISIBLE_STEP);
+                let radiance = planck(wavelength, temperature);
+                let (x, y, z) = colour_matching(wavelength);
+                Xyz {
+                    x: acc.x + radiance * x,
+                    y: acc.y + radiance * y,
+                    z: acc.z + radiance * z,
+                }
+            },
+        )
+    }
+
+    /// The chromaticity of the colour. A black body too cold to give off any visible light in
+    /// an f64 has the chromaticity of the red end of the spectrum, which colder black bodies
+    /// approach.
+    pub fn chromaticity(&self) -> Chromaticity {
+        let total = self.x + self.y + self.z;
+        if total <= 0.0 {
+            return REDDEST;
+        }
+        Chromaticity {
+            x: self.x / total,
+            y: self.y / total,
+        }
+    }
+
+    pub fn scale(&self, factor: f64) -> Xyz {
+        Xyz {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+
+    /// Convert to linear sRGB. Colours outside the sRGB gamut have their negative channels
+    /// clamped to zero.
+    pub fn to_linear_srgb(self) -> LinearRgb {
+        let [r, g, b] = XYZ_TO_LINEAR_SRGB
+            .map(|row| f64::max(0.0, row[0] * self.x + row[1] * self.y + row[2] * self.z));
+        LinearRgb { r, g, b }
+    }
+}
+
+/// Apply the sRGB transfer function to a linear channel, clamping to the displayable range
+fn gamma_encode(channel: f64) -> f64 {
+    let channel = channel.clamp(0.0, 1.0);
+    if channel <= 0.0031308 {
+        12.92 * channel
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl LinearRgb {
+    pub fn to_srgb(self) -> Srgb {
+        Srgb {
+            r: gamma_encode(self.r),
+            g: gamma_encode(self.g),
+            b: gamma_encode(self.b),
+        }
+    }
+
+    /// Scale the colour so its brightest channel is exactly 1. Black stays black.
+    pub fn normalised(&self) -> LinearRgb {
+        let brightest = self.r.max(self.g).max(self.b);
+        if brightest <= 0.0 {
+            return LinearRgb {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            };
+        }
+        LinearRgb {
+            r: self.r / brightest,
+            g: self.g / brightest,
+            b: self.b / brightest,
+        }
+    }
+}
+
+impl Srgb {
+    /// Convert to the 0-255 representation used by `calculate_colour` and `bv_to_rgb`
+    pub fn to_rgb(self) -> RGB {
+        RGB {
+            r: (self.r * 255.0).round(),
+            g: (self.g * 255.0).round(),
+            b: (self.b * 255.0).round(),
+        }
+    }
+}
+
+/// The colour of a black body in each of the representations a renderer may need.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StarColour {
+    pub xyz: Xyz,
+    pub chromaticity: Chromaticity,
+    pub linear: LinearRgb,
+    pub srgb: Srgb,
+}
+
+impl StarColour {
+    /// The colour of a black body at the given temperature.
+    ///
+    /// With `normalise_brightness` the colour is scaled so its brightest linear channel is 1,
+    /// giving the same brightness at every temperature. Otherwise brightness is relative to the
+    /// surface of the sun, which has a luminance `Y` of 1, so hotter stars exceed 1 and are
+    /// clipped in the gamma encoded sRGB.
+    pub fn from_temperature(
+        temperature: ThermodynamicTemperature,
+        normalise_brightness: bool,
+    ) -> StarColour {
+        let reference = Xyz::from_temperature(ThermodynamicTemperature::new::<kelvin>(
+            REFERENCE_TEMPERATURE,
+        ));
+        let xyz = Xyz::from_temperature(temperature).scale(1.0 / reference.y);
+        let linear = if normalise_brightness {
+            xyz.to_linear_srgb().normalised()
+        } else {
+            xyz.to_linear_srgb()
+        };
+
+        StarColour {
+            xyz,
+            chromaticity: xyz.chromaticity(),
+            linear,
+            srgb: linear.to_srgb(),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::colour::colour::{colour_matching, StarColour, Xyz};
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, ThermodynamicTemperature};
+    use uom::si::length::nanometer;
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    fn temperature(kelvins: f64) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(kelvins)
+    }
+
+    #[test]
+    fn colour_matching_functions_peak_in_the_right_places() {
+        let (_, y, _) = colour_matching(Length::new::<nanometer>(555.0));
+        assert_approx_eq!(f64, y, 1.0, epsilon = 0.02);
+
+        let (x, _, _) = colour_matching(Length::new::<nanometer>(600.0));
+        assert_approx_eq!(f64, x, 1.06, epsilon = 0.02);
+
+        let (_, _, z) = colour_matching(Length::new::<nanometer>(445.0));
+        assert_approx_eq!(f64, z, 1.78, epsilon = 0.05);
+    }
+
+    #[test]
+    fn blackbody_chromaticity_follows_planckian_locus() {
+        // CIE standard illuminant A is a 2856K black body
+        let a = Xyz::from_temperature(temperature(2856.0)).chromaticity();
+        assert_approx_eq!(f64, a.x, 0.4476, epsilon = 0.003);
+        assert_approx_eq!(f64, a.y, 0.4074, epsilon = 0.003);
+
+        let white = Xyz::from_temperature(temperature(6504.0)).chromaticity();
+        assert_approx_eq!(f64, white.x, 0.3135, epsilon = 0.003);
+        assert_approx_eq!(f64, white.y, 0.3236, epsilon = 0.003);
+    }
+
+    #[test]
+    fn star_colours_across_temperatures() {
+        let red = StarColour::from_temperature(temperature(3000.0), true);
+        assert_eq!(red.linear.r, 1.0);
+        assert!(red.linear.b < red.linear.g && red.linear.g < red.linear.r);
+
+        let blue = StarColour::from_temperature(temperature(30000.0), true);
+        assert_eq!(blue.linear.b, 1.0);
+        assert!(blue.linear.r < blue.linear.g && blue.linear.g < blue.linear.b);
+
+        // a 6500K black body is close to the sRGB white point
+        let white = StarColour::from_temperature(temperature(6504.0), true);
+        assert!(white.linear.r > 0.9 && white.linear.g > 0.9 && white.linear.b > 0.9);
+
+        // colours change smoothly with temperature
+        (20..400).for_each(|hundreds| {
+            let a = StarColour::from_temperature(temperature(hundreds as f64 * 100.0), true);
+            let b =
+                StarColour::from_temperature(temperature(hundreds as f64 * 100.0 + 100.0), true);
+            assert!((a.linear.r - b.linear.r).abs() < 0.05);
+            assert!((a.linear.g - b.linear.g).abs() < 0.05);
+            assert!((a.linear.b - b.linear.b).abs() < 0.05);
+        });
+    }
+
+    #[test]
+    fn cold_black_bodies_are_black() {
+        // a 10K black body's visible light underflows to nothing
+        let xyz = Xyz::from_temperature(temperature(10.0));
+        assert_eq!(xyz.y, 0.0);
+
+        let chromaticity = xyz.chromaticity();
+        assert!(chromaticity.x.is_finite() && chromaticity.y.is_finite());
+        assert!(chromaticity.x > 0.7);
+
+        for normalise_brightness in [true, false] {
+            let colour = StarColour::from_temperature(temperature(10.0), normalise_brightness);
+            assert_eq!(colour.srgb.to_rgb().r, 0.0);
+            assert_eq!(colour.linear.r, 0.0);
+            assert_eq!(colour.chromaticity, chromaticity);
+        }
+    }
+
+    #[test]
+    fn brightness_relative_to_sun() {
+        let sun = StarColour::from_temperature(temperature(5778.0), false);
+        assert_approx_eq!(f64, sun.xyz.y, 1.0, ulps = 2);
+
+        let hot = StarColour::from_temperature(temperature(10000.0), false);
+        assert!(hot.xyz.y > 1.0);
+        assert!(hot.srgb.r <= 1.0 && hot.srgb.g <= 1.0 && hot.srgb.b <= 1.0);
+    }
+
+    #[test]
+    fn srgb_encoding() {
+        let white = StarColour::from_temperature(temperature(6504.0), true);
+        let rgb = white.srgb.to_rgb();
+        assert!(rgb.r > 240.0 && rgb.g > 240.0 && rgb.b > 240.0);
+
+        // gamma encoding brightens mid tones
+        let red = StarColour::from_temperature(temperature(3000.0), true);
+        assert!(red.srgb.g > red.linear.g);
+    }
+}
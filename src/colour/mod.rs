@@ -0,0 +1,2 @@
+pub mod colour;
+mod colour_test;
@@ -1,33 +1,14 @@
-use num::PrimInt;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 use std::ops::Sub;
 
-use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
-use rand_seeder::Seeder;
 
 use num::traits::Pow;
 use serde::Serialize;
 
-fn calculate_hash<T: Hash>(t: T) -> u64
-where
-    T: PrimInt,
-{
-    let mut s = DefaultHasher::new();
-    t.hash(&mut s);
-    s.finish()
-}
-
-fn generate_hash(x: i64, y: i64, z: i64) -> u64 {
-    let h_x = calculate_hash(x + 1);
-    let h_y = calculate_hash(y + 2);
-    let h_z = calculate_hash(z + 3);
-    let mut res: i128 = h_x as i128;
-    res = res + h_y as i128;
-    res = res + h_z as i128;
-    calculate_hash(res)
-}
+use crate::hash::hash::{hash_coordinates, HashVersion, CURRENT_HASH_VERSION};
+use crate::coordinates::position::{GalacticPosition, SectorPosition};
+use crate::hash::seed::Seed;
+use uom::si::f64::Length;
 
 #[derive(Debug, Serialize, Copy, Clone)]
 pub struct Coordinates {
@@ -39,10 +20,37 @@ pub struct Coordinates {
 
 impl Coordinates {
     pub fn new(x: i64, y: i64, z: i64) -> Coordinates {
-        let h = generate_hash(x, y, z);
+        Coordinates::with_hash_version(x, y, z, CURRENT_HASH_VERSION)
+    }
+
+    /// Create coordinates whose hash is generated by a specific version of the hash, such as
+    /// the version a saved universe was created with.
+    pub fn with_hash_version(x: i64, y: i64, z: i64, version: HashVersion) -> Coordinates {
+        let h = hash_coordinates(x, y, z, version);
         Coordinates { x, y, z, hash: h }
     }
 
+    /// The root seed of everything generated at these coordinates
+    pub fn seed(&self) -> Seed {
+        Seed(self.hash)
+    }
+
+    /// The same coordinates with their hash regenerated by another version of the hash.
+    /// Used to migrate seeds saved with an older version.
+    pub fn rehash(&self, version: HashVersion) -> Coordinates {
+        Coordinates::with_hash_version(self.x, self.y, self.z, version)
+    }
+
+    /// The centre of this cell relative to the galactic centre, for cells `sector_size` wide
+    pub fn position(&self, sector_size: Length) -> GalacticPosition {
+        SectorPosition::centre(*self).to_galactic(sector_size)
+    }
+
+    /// The distance between the centres of two cells that are `sector_size` wide
+    pub fn distance(&self, other: &Coordinates, sector_size: Length) -> Length {
+        SectorPosition::centre(*self).distance(&SectorPosition::centre(*other), sector_size)
+    }
+
     pub fn get_distance(coordinates_alpha: &Coordinates, coordinates_beta: &Coordinates) -> f32 {
         let alpha: f32 = (coordinates_alpha.x as f32 - coordinates_beta.x as f32).pow(2);
         let beta: f32 = (coordinates_alpha.y as f32 - coordinates_beta.y as f32).pow(2);
@@ -71,6 +79,11 @@ impl Sub for Coordinates {
 pub trait Cartesian {
     fn coordinates(&self) -> &Coordinates;
     fn get_rng(&self) -> ChaCha20Rng {
-        ChaCha20Rng::seed_from_u64(self.coordinates().hash)
+        self.seed().rng()
+    }
+
+    /// The root seed of this entity, to derive independent streams for each of its parts from
+    fn seed(&self) -> Seed {
+        self.coordinates().seed()
     }
 }
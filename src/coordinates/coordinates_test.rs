@@ -1,14 +1,16 @@
 #[cfg(test)]
 mod tests {
     use crate::coordinates::coordinates::Coordinates;
+    use crate::hash::hash::HashVersion;
     use std::borrow::BorrowMut;
+    use std::collections::HashSet;
     use uom::si::length::{kilometer, meter, Length};
     // use xorshift::{Rng, SeedableRng, Xoroshiro128};
 
     #[test]
     fn coordinates_hash_correctly() {
         let coordinates = Coordinates::new(0, 0, 0);
-        assert_eq!(coordinates.hash, 2720667137516918932);
+        assert_eq!(coordinates.hash, 16496223809850827564);
         assert_eq!(coordinates.x, 0);
         assert_eq!(coordinates.y, 0);
         assert_eq!(coordinates.z, 0);
@@ -24,12 +26,63 @@ mod tests {
     #[test]
     fn coordinates_hash_correctly_with_negative_values() {
         let coordinates = Coordinates::new(-1, -2, -3);
-        assert_eq!(coordinates.hash, 14052202922209838698);
+        assert_eq!(coordinates.hash, 15088408851223415532);
         assert_eq!(coordinates.x, -1);
         assert_eq!(coordinates.y, -2);
         assert_eq!(coordinates.z, -3);
     }
 
+    #[test]
+    fn coordinates_hash_stable_golden_values() {
+        // these must never change, saved universes depend on them
+        assert_eq!(Coordinates::new(1, 2, 3).hash, 11371290574765008318);
+        assert_eq!(Coordinates::new(1 << 40, -7, 12).hash, 10065989114702775210);
+        assert_eq!(
+            Coordinates::new(1, 2, 3).hash,
+            Coordinates::with_hash_version(1, 2, 3, HashVersion::SplitMix64).hash
+        );
+    }
+
+    #[test]
+    fn coordinates_hash_legacy_version() {
+        let legacy = Coordinates::with_hash_version(0, 0, 0, HashVersion::Legacy);
+        assert_eq!(legacy.hash, 2720667137516918932);
+        let legacy = Coordinates::with_hash_version(-1, -2, -3, HashVersion::Legacy);
+        assert_eq!(legacy.hash, 14052202922209838698);
+
+        let migrated = legacy.rehash(HashVersion::SplitMix64);
+        assert_eq!((migrated.x, migrated.y, migrated.z), (-1, -2, -3));
+        assert_eq!(migrated.hash, Coordinates::new(-1, -2, -3).hash);
+    }
+
+    #[test]
+    fn coordinates_hash_without_collisions() {
+        // every permutation and reflection of a small cube around the origin
+        let mut hashes = HashSet::new();
+        for x in -10..=10 {
+            for y in -10..=10 {
+                for z in -10..=10 {
+                    assert!(hashes.insert(Coordinates::new(x, y, z).hash));
+                }
+            }
+        }
+
+        // the legacy hash sums a hash of each offset axis, so (0, 1, 2) and (2, -1, 2) both
+        // sum the hashes of 1, 3 and 5
+        assert_eq!(
+            Coordinates::with_hash_version(0, 1, 2, HashVersion::Legacy).hash,
+            Coordinates::with_hash_version(2, -1, 2, HashVersion::Legacy).hash
+        );
+        assert_ne!(
+            Coordinates::new(0, 1, 2).hash,
+            Coordinates::new(2, -1, 2).hash
+        );
+        assert_ne!(
+            Coordinates::new(0, 1, 2).hash,
+            Coordinates::new(2, 1, 0).hash
+        );
+    }
+
     #[test]
     fn coordinates_equality_check_works() {
         let coordinates1 = Coordinates::new(1, 2, 3);
@@ -1,2 +1,8 @@
 pub mod coordinates;
 mod coordinates_test;
+pub mod position;
+mod position_test;
+pub mod sky;
+mod sky_test;
+pub mod spatial_index;
+mod spatial_index_test;
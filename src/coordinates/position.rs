@@ -0,0 +1,203 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use serde::Serialize;
+use uom::si::f64::Length;
+use uom::si::length::meter;
+
+use crate::coordinates::coordinates::Coordinates;
+
+/// A displacement in space with a unit attached to every axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Position {
+    pub x: Length,
+    pub y: Length,
+    pub z: Length,
+}
+
+impl Position {
+    pub fn new(x: Length, y: Length, z: Length) -> Position {
+        Position { x, y, z }
+    }
+
+    pub fn origin() -> Position {
+        let zero = Length::new::<meter>(0.0);
+        Position::new(zero, zero, zero)
+    }
+
+    /// The distance from the origin of the frame
+    pub fn magnitude(&self) -> Length {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    pub fn distance(&self, other: &Position) -> Length {
+        (*self - *other).magnitude()
+    }
+
+    pub fn to_array(self) -> [Length; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl From<[Length; 3]> for Position {
+    fn from(axes: [Length; 3]) -> Position {
+        Position::new(axes[0], axes[1], axes[2])
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Position) -> Position {
+        Position::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, rhs: Position) -> Position {
+        Position::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Position {
+    type Output = Position;
+
+    fn neg(self) -> Position {
+        Position::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Position {
+    type Output = Position;
+
+    fn mul(self, rhs: f64) -> Position {
+        Position::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// A position relative to the centre of the galaxy, with the disk in the x-y plane.
+///
+/// An `f64` in metres only resolves to around a hundred kilometres at the edge of the galaxy.
+/// Use a `SectorPosition` or `LocalPosition` where finer detail is needed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GalacticPosition(pub Position);
+
+/// A position within a sector, as an offset from the centre of the sector's cell.
+/// Offsets are kept within half a sector of the centre along each axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SectorPosition {
+    pub sector: Coordinates,
+    pub offset: Position,
+}
+
+/// A position relative to some object, such as a core relative to the centre of its cloud or a
+/// star relative to the centre of its cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LocalPosition {
+    pub origin: SectorPosition,
+    pub offset: Position,
+}
+
+/// The position of the centre of a cell, `cells` sectors from the origin along each axis.
+fn cell_offset(cells: [i64; 3], sector_size: Length) -> Position {
+    Position::from(cells.map(|cell| sector_size * cell as f64))
+}
+
+/// Split a length along one axis into a whole number of sectors and what remains.
+fn split_axis(length: Length, sector_size: Length) -> (i64, Length) {
+    let cells = (length.value / sector_size.value).round();
+    (cells as i64, length - sector_size * cells)
+}
+
+impl GalacticPosition {
+    pub fn to_sector(self, sector_size: Length) -> SectorPosition {
+        let (x, offset_x) = split_axis(self.0.x, sector_size);
+        let (y, offset_y) = split_axis(self.0.y, sector_size);
+        let (z, offset_z) = split_axis(self.0.z, sector_size);
+        SectorPosition {
+            sector: Coordinates::new(x, y, z),
+            offset: Position::new(offset_x, offset_y, offset_z),
+        }
+    }
+
+    pub fn distance(&self, other: &GalacticPosition) -> Length {
+        self.0.distance(&other.0)
+    }
+}
+
+impl SectorPosition {
+    /// The centre of a sector
+    pub fn centre(sector: Coordinates) -> SectorPosition {
+        SectorPosition {
+            sector,
+            offset: Position::origin(),
+        }
+    }
+
+    pub fn to_galactic(self, sector_size: Length) -> GalacticPosition {
+        let cells = [self.sector.x, self.sector.y, self.sector.z];
+        GalacticPosition(cell_offset(cells, sector_size) + self.offset)
+    }
+
+    /// The displacement from `other` to this position. Whole sectors are counted before the
+    /// offsets are added, so nearby positions far from the galactic centre keep their precision.
+    pub fn displacement_from(&self, other: &SectorPosition, sector_size: Length) -> Position {
+        let cells = [
+            self.sector.x - other.sector.x,
+            self.sector.y - other.sector.y,
+            self.sector.z - other.sector.z,
+        ];
+        cell_offset(cells, sector_size) + (self.offset - other.offset)
+    }
+
+    pub fn distance(&self, other: &SectorPosition, sector_size: Length) -> Length {
+        self.displacement_from(other, sector_size).magnitude()
+    }
+
+    /// Move the offset back within half a sector of the centre, changing sector if needed.
+    pub fn normalised(&self, sector_size: Length) -> SectorPosition {
+        let (x, offset_x) = split_axis(self.offset.x, sector_size);
+        let (y, offset_y) = split_axis(self.offset.y, sector_size);
+        let (z, offset_z) = split_axis(self.offset.z, sector_size);
+        SectorPosition {
+            sector: Coordinates::new(self.sector.x + x, self.sector.y + y, self.sector.z + z),
+            offset: Position::new(offset_x, offset_y, offset_z),
+        }
+    }
+
+    /// This position relative to `origin`
+    pub fn to_local(self, origin: SectorPosition, sector_size: Length) -> LocalPosition {
+        LocalPosition {
+            origin,
+            offset: self.displacement_from(&origin, sector_size),
+        }
+    }
+}
+
+impl LocalPosition {
+    pub fn new(origin: SectorPosition, offset: Position) -> LocalPosition {
+        LocalPosition { origin, offset }
+    }
+
+    pub fn to_sector(self, sector_size: Length) -> SectorPosition {
+        SectorPosition {
+            sector: self.origin.sector,
+            offset: self.origin.offset + self.offset,
+        }
+        .normalised(sector_size)
+    }
+
+    pub fn to_galactic(self, sector_size: Length) -> GalacticPosition {
+        self.to_sector(sector_size).to_galactic(sector_size)
+    }
+
+    /// The distance between two local positions, which may have different origins
+    pub fn distance(&self, other: &LocalPosition, sector_size: Length) -> Length {
+        if self.origin == other.origin {
+            return self.offset.distance(&other.offset);
+        }
+        self.to_sector(sector_size)
+            .distance(&other.to_sector(sector_size), sector_size)
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::coordinates::Coordinates;
+    use crate::coordinates::position::{GalacticPosition, LocalPosition, Position, SectorPosition};
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::Length;
+    use uom::si::length::{astronomical_unit, kilometer, light_year, parsec};
+
+    fn sector_size() -> Length {
+        Length::new::<light_year>(100.0)
+    }
+
+    fn ly(x: f64, y: f64, z: f64) -> Position {
+        Position::new(
+            Length::new::<light_year>(x),
+            Length::new::<light_year>(y),
+            Length::new::<light_year>(z),
+        )
+    }
+
+    #[test]
+    fn position_arithmetic() {
+        let a = ly(3.0, 4.0, 0.0);
+        assert_approx_eq!(f64, a.magnitude().get::<light_year>(), 5.0, epsilon = 1e-12);
+        assert_eq!(a + ly(1.0, 1.0, 1.0) - ly(1.0, 1.0, 1.0), a);
+        assert_eq!(-a, a * -1.0);
+        assert_approx_eq!(
+            f64,
+            a.distance(&Position::origin()).get::<light_year>(),
+            5.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn galactic_to_sector_round_trip() {
+        let galactic = GalacticPosition(ly(26_049.0, -151.0, 12.5));
+        let sector = galactic.to_sector(sector_size());
+        assert_eq!(sector.sector, Coordinates::new(260, -2, 0));
+        assert_approx_eq!(
+            f64,
+            sector.offset.x.get::<light_year>(),
+            49.0,
+            epsilon = 1e-6
+        );
+        assert_approx_eq!(
+            f64,
+            sector.offset.y.get::<light_year>(),
+            49.0,
+            epsilon = 1e-6
+        );
+
+        let back = sector.to_galactic(sector_size());
+        assert_approx_eq!(
+            f64,
+            back.distance(&galactic).get::<kilometer>(),
+            0.0,
+            epsilon = 1e3
+        );
+    }
+
+    #[test]
+    fn local_positions_keep_precision() {
+        // an au offset from a cloud near the edge of the galaxy
+        let cloud = SectorPosition::centre(Coordinates::new(500, 0, 0));
+        let star = LocalPosition::new(
+            cloud,
+            Position::new(
+                Length::new::<astronomical_unit>(1.0),
+                Length::new::<astronomical_unit>(0.0),
+                Length::new::<astronomical_unit>(0.0),
+            ),
+        );
+        let planet = LocalPosition::new(
+            cloud,
+            Position::new(
+                Length::new::<astronomical_unit>(1.0),
+                Length::new::<astronomical_unit>(0.0),
+                Length::new::<kilometer>(1.0),
+            ),
+        );
+        assert_approx_eq!(
+            f64,
+            star.distance(&planet, sector_size()).get::<kilometer>(),
+            1.0,
+            epsilon = 1e-6
+        );
+
+        // converting through the sector frame keeps the same precision
+        let star_sector = star.to_sector(sector_size());
+        let planet_sector = planet.to_sector(sector_size());
+        assert_approx_eq!(
+            f64,
+            planet_sector
+                .distance(&star_sector, sector_size())
+                .get::<kilometer>(),
+            1.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn local_positions_cross_sectors() {
+        let origin = SectorPosition::centre(Coordinates::new(1, 1, 1));
+        let local = LocalPosition::new(origin, ly(120.0, -60.0, 0.0));
+        let sector = local.to_sector(sector_size());
+        assert_eq!(sector.sector, Coordinates::new(2, 0, 1));
+        assert_approx_eq!(
+            f64,
+            sector.offset.x.get::<light_year>(),
+            20.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            sector.offset.y.get::<light_year>(),
+            40.0,
+            epsilon = 1e-9
+        );
+
+        let relative = sector.to_local(origin, sector_size());
+        assert_approx_eq!(
+            f64,
+            relative.offset.distance(&local.offset).get::<light_year>(),
+            0.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn coordinates_distance_as_length() {
+        let a = Coordinates::new(1, 2, 3);
+        let b = Coordinates::new(9, 2, 8);
+        let distance = a.distance(&b, Length::new::<parsec>(1.0));
+        assert_approx_eq!(
+            f64,
+            distance.get::<parsec>(),
+            Coordinates::get_distance(&a, &b) as f64,
+            epsilon = 1e-5
+        );
+        assert_eq!(
+            a.position(Length::new::<parsec>(2.0)).0.z,
+            Length::new::<parsec>(6.0)
+        );
+    }
+}
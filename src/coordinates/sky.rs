@@ -0,0 +1,296 @@
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+
+use uom::si::angle::{degree, radian};
+use uom::si::f64::{Angle, Length};
+
+use crate::coordinates::position::Position;
+use crate::units::units::angle::hour_angle;
+
+/// The tilt of the Earth's axis against the ecliptic at the J2000 epoch.
+const OBLIQUITY_OF_THE_ECLIPTIC: f64 = 23.439_281;
+
+/// The number of decimal places shown on the seconds of right ascension and declination.
+const HMS_DECIMALS: i32 = 2;
+const DMS_DECIMALS: i32 = 1;
+
+/// Rotates equatorial (ICRS) unit vectors into galactic unit vectors. Each row is a galactic
+/// axis expressed in equatorial coordinates (Hipparcos, ESA 1997).
+const EQUATORIAL_TO_GALACTIC: [[f64; 3]; 3] = [
+    [
+        -0.054_875_560_416_215_4,
+        -0.873_437_090_234_885,
+        -0.483_835_015_548_713_2,
+    ],
+    [
+        0.494_109_427_875_583_7,
+        -0.444_829_629_960_011_2,
+        0.746_982_244_497_219,
+    ],
+    [
+        -0.867_666_149_019_004_7,
+        -0.198_076_373_431_201_5,
+        0.455_983_776_175_066_9,
+    ],
+];
+
+/// A point described by its direction and distance from an origin.
+/// Longitude runs anticlockwise from the x axis in the x-y plane, latitude up from that plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SphericalCoordinates {
+    pub longitude: Angle,
+    pub latitude: Angle,
+    pub distance: Length,
+}
+
+impl SphericalCoordinates {
+    pub fn from_cartesian(position: Position) -> SphericalCoordinates {
+        let distance = position.magnitude();
+        let planar = (position.x * position.x + position.y * position.y).sqrt();
+        SphericalCoordinates {
+            longitude: normalise(Angle::new::<radian>(
+                position.y.value.atan2(position.x.value),
+            )),
+            latitude: Angle::new::<radian>(position.z.value.atan2(planar.value)),
+            distance,
+        }
+    }
+
+    pub fn to_cartesian(self) -> Position {
+        let [x, y, z] = unit_vector(self.longitude, self.latitude);
+        Position::new(self.distance * x, self.distance * y, self.distance * z)
+    }
+}
+
+/// The reference plane and zero point a sky position is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkyFrame {
+    /// Right ascension and declination, against the celestial equator (ICRS / J2000)
+    Equatorial,
+    /// Galactic longitude and latitude (l, b), against the plane of the galaxy with l = 0
+    /// towards the galactic centre
+    Galactic,
+    /// Ecliptic longitude and latitude, against the plane of the Earth's orbit (J2000)
+    Ecliptic,
+}
+
+/// A direction on the sky in one of the standard celestial frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyCoordinates {
+    pub frame: SkyFrame,
+    pub longitude: Angle,
+    pub latitude: Angle,
+}
+
+impl SkyCoordinates {
+    pub fn new(frame: SkyFrame, longitude: Angle, latitude: Angle) -> SkyCoordinates {
+        SkyCoordinates {
+            frame,
+            longitude: normalise(longitude),
+            latitude,
+        }
+    }
+
+    /// The direction of a displacement in the galaxy.
+    ///
+    /// The galaxy's axes are taken to be aligned with the galactic frame: x points from the
+    /// observer's side of the galaxy towards the centre (l = 0), y along the direction of
+    /// rotation (l = 90°) and z to the north galactic pole.
+    pub fn from_displacement(displacement: Position) -> SkyCoordinates {
+        let spherical = SphericalCoordinates::from_cartesian(displacement);
+        SkyCoordinates::new(SkyFrame::Galactic, spherical.longitude, spherical.latitude)
+    }
+
+    /// The direction as a unit vector in the equatorial frame
+    fn equatorial_vector(&self) -> [f64; 3] {
+        let vector = unit_vector(self.longitude, self.latitude);
+        match self.frame {
+            SkyFrame::Equatorial => vector,
+            SkyFrame::Galactic => multiply(&transpose(&EQUATORIAL_TO_GALACTIC), vector),
+            SkyFrame::Ecliptic => rotate_about_x(vector, -OBLIQUITY_OF_THE_ECLIPTIC),
+        }
+    }
+
+    /// The same direction expressed in another frame
+    pub fn to_frame(self, frame: SkyFrame) -> SkyCoordinates {
+        if frame == self.frame {
+            return self;
+        }
+        let equatorial = self.equatorial_vector();
+        let [x, y, z] = match frame {
+            SkyFrame::Equatorial => equatorial,
+            SkyFrame::Galactic => multiply(&EQUATORIAL_TO_GALACTIC, equatorial),
+            SkyFrame::Ecliptic => rotate_about_x(equatorial, OBLIQUITY_OF_THE_ECLIPTIC),
+        };
+        SkyCoordinates::new(
+            frame,
+            Angle::new::<radian>(y.atan2(x)),
+            Angle::new::<radian>(z.atan2((x * x + y * y).sqrt())),
+        )
+    }
+
+    /// The angle between two directions, which may be in different frames
+    pub fn separation(&self, other: &SkyCoordinates) -> Angle {
+        let a = self.equatorial_vector();
+        let b = other.equatorial_vector();
+        let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let cross = [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ];
+        let sine = (cross[0].powi(2) + cross[1].powi(2) + cross[2].powi(2)).sqrt();
+        // atan2 stays accurate for both tiny and near antipodal separations
+        Angle::new::<radian>(sine.atan2(dot))
+    }
+}
+
+impl Display for SkyCoordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.frame {
+            SkyFrame::Equatorial => write!(
+                f,
+                "RA {} Dec {}",
+                Hms::from_angle(self.longitude),
+                Dms::from_angle(self.latitude)
+            ),
+            SkyFrame::Galactic => write!(
+                f,
+                "l {:.4}° b {:+.4}°",
+                self.longitude.get::<degree>(),
+                self.latitude.get::<degree>()
+            ),
+            SkyFrame::Ecliptic => write!(
+                f,
+                "λ {:.4}° β {:+.4}°",
+                self.longitude.get::<degree>(),
+                self.latitude.get::<degree>()
+            ),
+        }
+    }
+}
+
+/// An angle written in hours, minutes and seconds, as used for right ascension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hms {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: f64,
+}
+
+impl Hms {
+    /// Split an angle into hours, minutes and seconds, wrapping it into a single turn first
+    pub fn from_angle(angle: Angle) -> Hms {
+        let hours = normalise(angle).get::<hour_angle>();
+        let (whole_hours, minutes, seconds) = sexagesimal(hours, HMS_DECIMALS);
+        Hms {
+            hours: whole_hours % 24,
+            minutes,
+            seconds,
+        }
+    }
+
+    pub fn to_angle(self) -> Angle {
+        Angle::new::<hour_angle>(
+            self.hours as f64 + self.minutes as f64 / 60.0 + self.seconds / 3600.0,
+        )
+    }
+}
+
+impl Display for Hms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}h {:02}m {:0width$.decimals$}s",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            width = HMS_DECIMALS as usize + 3,
+            decimals = HMS_DECIMALS as usize
+        )
+    }
+}
+
+/// An angle written in degrees, arcminutes and arcseconds, as used for declination.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dms {
+    pub negative: bool,
+    pub degrees: u32,
+    pub minutes: u32,
+    pub seconds: f64,
+}
+
+impl Dms {
+    pub fn from_angle(angle: Angle) -> Dms {
+        let degrees = angle.get::<degree>();
+        let (whole_degrees, minutes, seconds) = sexagesimal(degrees.abs(), DMS_DECIMALS);
+        Dms {
+            negative: degrees < 0.0,
+            degrees: whole_degrees,
+            minutes,
+            seconds,
+        }
+    }
+
+    pub fn to_angle(self) -> Angle {
+        let magnitude = self.degrees as f64 + self.minutes as f64 / 60.0 + self.seconds / 3600.0;
+        Angle::new::<degree>(if self.negative { -magnitude } else { magnitude })
+    }
+}
+
+impl Display for Dms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{:02}° {:02}′ {:0width$.decimals$}″",
+            if self.negative { '-' } else { '+' },
+            self.degrees,
+            self.minutes,
+            self.seconds,
+            width = DMS_DECIMALS as usize + 3,
+            decimals = DMS_DECIMALS as usize
+        )
+    }
+}
+
+/// Split a non-negative value into whole units, sixtieths and three-thousand-six-hundredths.
+/// Seconds are rounded to `decimals` places and carried so they never read 60.
+fn sexagesimal(value: f64, decimals: i32) -> (u32, u32, f64) {
+    let scale = 10.0_f64.powi(decimals);
+    let total = (value * 3600.0 * scale).round() / scale;
+    let whole = (total / 3600.0).floor();
+    let minutes = ((total - whole * 3600.0) / 60.0).floor();
+    let seconds = total - whole * 3600.0 - minutes * 60.0;
+    (whole as u32, minutes as u32, seconds)
+}
+
+/// Wrap an angle into a single turn, `[0, 2π)`
+fn normalise(angle: Angle) -> Angle {
+    Angle::new::<radian>(angle.get::<radian>().rem_euclid(2.0 * PI))
+}
+
+fn unit_vector(longitude: Angle, latitude: Angle) -> [f64; 3] {
+    let (longitude, latitude) = (longitude.get::<radian>(), latitude.get::<radian>());
+    [
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    ]
+}
+
+fn rotate_about_x(vector: [f64; 3], degrees: f64) -> [f64; 3] {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    [
+        vector[0],
+        cos * vector[1] + sin * vector[2],
+        -sin * vector[1] + cos * vector[2],
+    ]
+}
+
+fn multiply(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+fn transpose(matrix: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    [0, 1, 2].map(|column| [0, 1, 2].map(|row| matrix[row][column]))
+}
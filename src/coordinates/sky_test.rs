@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::position::Position;
+    use crate::coordinates::sky::{Dms, Hms, SkyCoordinates, SkyFrame, SphericalCoordinates};
+    use crate::formulae::formulae::{
+        calculate_absolute_magnitude, calculate_apparent_magnitude, calculate_distance_modulus,
+        length,
+    };
+    use crate::units::units::power::solar_luminosity;
+    use float_cmp::assert_approx_eq;
+    use uom::si::angle::degree;
+    use uom::si::f64::{Angle, Length, Power};
+    use uom::si::length::{light_year, parsec};
+
+    fn sky(frame: SkyFrame, longitude: f64, latitude: f64) -> SkyCoordinates {
+        SkyCoordinates::new(
+            frame,
+            Angle::new::<degree>(longitude),
+            Angle::new::<degree>(latitude),
+        )
+    }
+
+    fn assert_direction(actual: SkyCoordinates, longitude: f64, latitude: f64) {
+        assert_approx_eq!(
+            f64,
+            actual.longitude.get::<degree>(),
+            longitude,
+            epsilon = 1e-4
+        );
+        assert_approx_eq!(
+            f64,
+            actual.latitude.get::<degree>(),
+            latitude,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn sky_galactic_landmarks() {
+        let centre = sky(SkyFrame::Galactic, 0.0, 0.0).to_frame(SkyFrame::Equatorial);
+        assert_direction(centre, 266.40499, -28.93617);
+
+        let pole = sky(SkyFrame::Galactic, 0.0, 90.0).to_frame(SkyFrame::Equatorial);
+        assert_direction(pole, 192.85948, 27.12825);
+    }
+
+    #[test]
+    fn sky_ecliptic_landmarks() {
+        let equinox = sky(SkyFrame::Equatorial, 0.0, 0.0).to_frame(SkyFrame::Ecliptic);
+        assert_direction(equinox, 0.0, 0.0);
+
+        let pole = sky(SkyFrame::Ecliptic, 0.0, 90.0).to_frame(SkyFrame::Equatorial);
+        assert_approx_eq!(
+            f64,
+            pole.latitude.get::<degree>(),
+            90.0 - 23.439281,
+            epsilon = 1e-6
+        );
+        assert_approx_eq!(f64, pole.longitude.get::<degree>(), 270.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn sky_round_trips() {
+        let frames = [SkyFrame::Equatorial, SkyFrame::Galactic, SkyFrame::Ecliptic];
+        for (longitude, latitude) in [(10.0, 20.0), (359.0, -89.0), (180.0, 45.0)] {
+            for from in frames {
+                for to in frames {
+                    let start = sky(from, longitude, latitude);
+                    let back = start.to_frame(to).to_frame(from);
+                    assert!(start.separation(&back).get::<degree>() < 1e-9);
+                    assert!(start.separation(&start.to_frame(to)).get::<degree>() < 1e-9);
+                }
+            }
+        }
+        let a = sky(SkyFrame::Galactic, 0.0, 0.0);
+        let b = sky(SkyFrame::Galactic, 90.0, 0.0);
+        assert_approx_eq!(f64, a.separation(&b).get::<degree>(), 90.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn sky_spherical_conversion() {
+        let position = Position::new(
+            Length::new::<light_year>(-3.0),
+            Length::new::<light_year>(0.0),
+            Length::new::<light_year>(4.0),
+        );
+        let spherical = SphericalCoordinates::from_cartesian(position);
+        assert_approx_eq!(
+            f64,
+            spherical.distance.get::<light_year>(),
+            5.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            spherical.longitude.get::<degree>(),
+            180.0,
+            epsilon = 1e-9
+        );
+        let back = spherical.to_cartesian();
+        assert!(back.distance(&position).get::<light_year>() < 1e-9);
+
+        // straight towards the centre of the galaxy
+        let direction = SkyCoordinates::from_displacement(Position::new(
+            Length::new::<parsec>(8000.0),
+            Length::new::<parsec>(0.0),
+            Length::new::<parsec>(0.0),
+        ));
+        assert_eq!(direction.frame, SkyFrame::Galactic);
+        assert_direction(direction, 0.0, 0.0);
+    }
+
+    #[test]
+    fn sky_sexagesimal_formatting() {
+        let centre = sky(SkyFrame::Galactic, 0.0, 0.0).to_frame(SkyFrame::Equatorial);
+        assert_eq!(centre.to_string(), "RA 17h 45m 37.20s Dec -28° 56′ 10.2″");
+
+        let hms = Hms::from_angle(Angle::new::<degree>(-15.0));
+        assert_eq!((hms.hours, hms.minutes), (23, 0));
+        assert_approx_eq!(f64, hms.to_angle().get::<degree>(), 345.0, epsilon = 1e-9);
+
+        // seconds that round up carry into the minutes
+        let dms = Dms::from_angle(Angle::new::<degree>(10.0 - 1e-9));
+        assert_eq!(dms.to_string(), "+10° 00′ 00.0″");
+        let dms = Dms::from_angle(Angle::new::<degree>(-0.5));
+        assert!(dms.negative);
+        assert_approx_eq!(f64, dms.to_angle().get::<degree>(), -0.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn apparent_magnitude_from_distance() {
+        let sun = Power::new::<solar_luminosity>(1.0);
+        assert_approx_eq!(
+            f64,
+            calculate_apparent_magnitude(sun, Length::new::<parsec>(10.0)),
+            calculate_absolute_magnitude(sun),
+            epsilon = 1e-12
+        );
+        assert_approx_eq!(
+            f64,
+            calculate_distance_modulus(Length::new::<parsec>(100.0)),
+            5.0,
+            epsilon = 1e-12
+        );
+        assert_approx_eq!(
+            f64,
+            length::from_distance_modulus(5.0).get::<parsec>(),
+            100.0,
+            epsilon = 1e-9
+        );
+
+        // the sun seen from one astronomical unit is about magnitude -26.8
+        let au = Length::new::<uom::si::length::astronomical_unit>(1.0);
+        assert_approx_eq!(
+            f64,
+            calculate_apparent_magnitude(sun, au),
+            -26.83,
+            epsilon = 0.05
+        );
+    }
+}
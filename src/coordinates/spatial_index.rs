@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use uom::si::f64::Length;
+
+use crate::coordinates::coordinates::{Cartesian, Coordinates};
+
+/// A handle to an entry in a `SpatialIndex`, valid until the entry is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntryId(usize);
+
+type Bucket = (i64, i64, i64);
+
+/// Anything with coordinates, bucketed into a sparse grid so that nearby entries can be found
+/// without visiting the rest.
+///
+/// Each bucket spans `bucket_size` cells along each axis, and each cell is `sector_size` wide.
+/// Buckets that hold nothing take no memory, so the index can cover the whole galaxy.
+pub struct SpatialIndex<T: Cartesian> {
+    pub sector_size: Length,
+    bucket_size: i64,
+    buckets: HashMap<Bucket, Vec<EntryId>>,
+    entries: Vec<Option<T>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T: Cartesian> SpatialIndex<T> {
+    pub fn new(sector_size: Length, bucket_size: i64) -> SpatialIndex<T> {
+        SpatialIndex {
+            sector_size,
+            bucket_size: bucket_size.max(1),
+            buckets: HashMap::new(),
+            entries: vec![],
+            free: vec![],
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(&self, coordinates: &Coordinates) -> Bucket {
+        (
+            coordinates.x.div_euclid(self.bucket_size),
+            coordinates.y.div_euclid(self.bucket_size),
+            coordinates.z.div_euclid(self.bucket_size),
+        )
+    }
+
+    pub fn insert(&mut self, entry: T) -> EntryId {
+        let bucket = self.bucket(entry.coordinates());
+        let id = match self.free.pop() {
+            Some(index) => {
+                self.entries[index] = Some(entry);
+                EntryId(index)
+            }
+            None => {
+                self.entries.push(Some(entry));
+                EntryId(self.entries.len() - 1)
+            }
+        };
+        self.buckets.entry(bucket).or_default().push(id);
+        self.len += 1;
+        id
+    }
+
+    pub fn remove(&mut self, id: EntryId) -> Option<T> {
+        let entry = self.entries.get_mut(id.0)?.take()?;
+        let bucket = self.bucket(entry.coordinates());
+        if let Some(ids) = self.buckets.get_mut(&bucket) {
+            ids.retain(|other| *other != id);
+            if ids.is_empty() {
+                self.buckets.remove(&bucket);
+            }
+        }
+        self.free.push(id.0);
+        self.len -= 1;
+        Some(entry)
+    }
+
+    pub fn get(&self, id: EntryId) -> Option<&T> {
+        self.entries.get(id.0)?.as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EntryId, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_ref().map(|entry| (EntryId(index), entry)))
+    }
+
+    fn entries_in(&self, bucket: &Bucket) -> impl Iterator<Item = (EntryId, &T)> {
+        self.buckets
+            .get(bucket)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.get(*id).map(|entry| (*id, entry)))
+    }
+
+    /// Every entry whose coordinates lie within the box spanned by two corners, inclusive.
+    pub fn within_box(&self, corner: &Coordinates, opposite: &Coordinates) -> Vec<(EntryId, &T)> {
+        let min = Coordinates::new(
+            corner.x.min(opposite.x),
+            corner.y.min(opposite.y),
+            corner.z.min(opposite.z),
+        );
+        let max = Coordinates::new(
+            corner.x.max(opposite.x),
+            corner.y.max(opposite.y),
+            corner.z.max(opposite.z),
+        );
+        let (low, high) = (self.bucket(&min), self.bucket(&max));
+        let inside = |c: &Coordinates| {
+            (min.x..=max.x).contains(&c.x)
+                && (min.y..=max.y).contains(&c.y)
+                && (min.z..=max.z).contains(&c.z)
+        };
+
+        // a large box over a sparse index holds far more buckets than are occupied, so visit the
+        // occupied ones instead
+        let span = |low: i64, high: i64| (high as i128 - low as i128 + 1) as u128;
+        let volume = span(low.0, high.0)
+            .saturating_mul(span(low.1, high.1))
+            .saturating_mul(span(low.2, high.2));
+        let buckets: Vec<Bucket> = if volume > self.buckets.len() as u128 {
+            self.buckets
+                .keys()
+                .filter(|(x, y, z)| {
+                    (low.0..=high.0).contains(x)
+                        && (low.1..=high.1).contains(y)
+                        && (low.2..=high.2).contains(z)
+                })
+                .copied()
+                .collect()
+        } else {
+            let mut buckets = vec![];
+            for x in low.0..=high.0 {
+                for y in low.1..=high.1 {
+                    for z in low.2..=high.2 {
+                        buckets.push((x, y, z));
+                    }
+                }
+            }
+            buckets
+        };
+
+        buckets
+            .iter()
+            .flat_map(|bucket| self.entries_in(bucket))
+            .filter(|(_, entry)| inside(entry.coordinates()))
+            .collect()
+    }
+
+    /// Every entry within `radius` of `centre`, with its distance, nearest first.
+    pub fn within_radius(
+        &self,
+        centre: &Coordinates,
+        radius: Length,
+    ) -> Vec<(EntryId, &T, Length)> {
+        let cells = (radius.value / self.sector_size.value).floor() as i64;
+        let corner = Coordinates::new(
+            centre.x.saturating_sub(cells),
+            centre.y.saturating_sub(cells),
+            centre.z.saturating_sub(cells),
+        );
+        let opposite = Coordinates::new(
+            centre.x.saturating_add(cells),
+            centre.y.saturating_add(cells),
+            centre.z.saturating_add(cells),
+        );
+
+        let mut found: Vec<(EntryId, &T, Length)> = self
+            .within_box(&corner, &opposite)
+            .into_iter()
+            .map(|(id, entry)| {
+                (
+                    id,
+                    entry,
+                    centre.distance(entry.coordinates(), self.sector_size),
+                )
+            })
+            .filter(|(_, _, distance)| *distance <= radius)
+            .collect();
+        found.sort_by(|a, b| a.2.value.total_cmp(&b.2.value));
+        found
+    }
+
+    /// The `count` entries nearest to `centre`, with their distances, nearest first.
+    ///
+    /// Searches outwards one shell of buckets at a time, stopping once nothing in an unvisited
+    /// bucket could be closer than the entries already found. Once a shell would hold more
+    /// buckets than are occupied, the occupied buckets left outside the search are scanned
+    /// directly instead.
+    pub fn nearest(&self, centre: &Coordinates, count: usize) -> Vec<(EntryId, &T, Length)> {
+        let mut found: Vec<(EntryId, &T, Length)> = vec![];
+        if count == 0 {
+            return found;
+        }
+        let home = self.bucket(centre);
+        let mut seen = 0;
+        let mut ring: i64 = 0;
+
+        loop {
+            if shell_size(ring) > self.buckets.len() as u128 {
+                // every occupied bucket from this ring outwards
+                found.extend(
+                    self.buckets
+                        .keys()
+                        .filter(|bucket| ring_of(home, **bucket) >= ring)
+                        .flat_map(|bucket| self.entries_in(bucket))
+                        .map(|(id, entry)| {
+                            let distance = centre.distance(entry.coordinates(), self.sector_size);
+                            (id, entry, distance)
+                        }),
+                );
+                found.sort_by(|a, b| a.2.value.total_cmp(&b.2.value));
+                found.truncate(count);
+                return found;
+            }
+
+            for offset in shell(ring) {
+                let bucket = (home.0 + offset.0, home.1 + offset.1, home.2 + offset.2);
+                for (id, entry) in self.entries_in(&bucket) {
+                    seen += 1;
+                    let distance = centre.distance(entry.coordinates(), self.sector_size);
+                    found.push((id, entry, distance));
+                }
+            }
+            found.sort_by(|a, b| a.2.value.total_cmp(&b.2.value));
+            found.truncate(count);
+
+            // anything in the next ring is at least `ring` buckets away
+            let unvisited = self.sector_size * (ring * self.bucket_size) as f64;
+            let complete = found.len() == count && found[count - 1].2 <= unvisited;
+            if complete || seen == self.len {
+                return found;
+            }
+            ring += 1;
+        }
+    }
+}
+
+/// How many rings of buckets out from `home` a bucket lies
+fn ring_of(home: Bucket, bucket: Bucket) -> i64 {
+    (bucket.0 - home.0)
+        .abs()
+        .max((bucket.1 - home.1).abs())
+        .max((bucket.2 - home.2).abs())
+}
+
+/// The number of buckets on the surface of the cube `ring` buckets out
+fn shell_size(ring: i64) -> u128 {
+    let outer = (2 * ring as u128 + 1).pow(3);
+    let inner = if ring == 0 {
+        0
+    } else {
+        (2 * ring as u128 - 1).pow(3)
+    };
+    outer - inner
+}
+
+/// The offsets of the buckets on the surface of the cube `ring` buckets out, without visiting
+/// the inside
+fn shell(ring: i64) -> impl Iterator<Item = Bucket> {
+    (-ring..=ring).flat_map(move |x| {
+        (-ring..=ring).flat_map(move |y| {
+            let edge = x.abs() == ring || y.abs() == ring;
+            // on the x or y faces every z is on the surface, elsewhere only the two z faces are
+            let zs: Vec<i64> = if edge {
+                (-ring..=ring).collect()
+            } else {
+                vec![-ring, ring]
+            };
+            zs.into_iter().map(move |z| (x, y, z))
+        })
+    })
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::coordinates::{Cartesian, Coordinates};
+    use crate::coordinates::spatial_index::SpatialIndex;
+    use crate::hash::seed::Seed;
+    use rand::Rng;
+    use uom::si::f64::Length;
+    use uom::si::length::light_year;
+
+    #[derive(Debug, PartialEq)]
+    struct Star {
+        coordinates: Coordinates,
+        name: usize,
+    }
+
+    impl Cartesian for Star {
+        fn coordinates(&self) -> &Coordinates {
+            &self.coordinates
+        }
+    }
+
+    fn index() -> SpatialIndex<Star> {
+        let mut rng = Seed(11).derive("stars").rng();
+        let mut index = SpatialIndex::new(Length::new::<light_year>(1.0), 8);
+        for name in 0..500 {
+            let mut axis = || rng.gen_range(-50..50);
+            index.insert(Star {
+                coordinates: Coordinates::new(axis(), axis(), axis()),
+                name,
+            });
+        }
+        index
+    }
+
+    #[test]
+    fn spatial_index_radius_matches_brute_force() {
+        let index = index();
+        let centre = Coordinates::new(3, -7, 12);
+        let radius = Length::new::<light_year>(20.0);
+
+        let found: Vec<usize> = index
+            .within_radius(&centre, radius)
+            .iter()
+            .map(|(_, star, _)| star.name)
+            .collect();
+        let mut expected: Vec<(usize, f64)> = index
+            .iter()
+            .map(|(_, star)| (star.name, star.coordinates - centre))
+            .filter(|(_, distance)| *distance <= 20.0)
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        assert!(!found.is_empty());
+        assert_eq!(found.len(), expected.len());
+        let distances = index.within_radius(&centre, radius);
+        assert!(distances.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    }
+
+    #[test]
+    fn spatial_index_nearest_matches_brute_force() {
+        let index = index();
+        for centre in [
+            Coordinates::new(0, 0, 0),
+            Coordinates::new(49, 49, 49),
+            Coordinates::new(-300, 10, 0),
+        ] {
+            let nearest = index.nearest(&centre, 10);
+            let mut expected: Vec<f64> = index
+                .iter()
+                .map(|(_, star)| star.coordinates - centre)
+                .collect();
+            expected.sort_by(|a, b| a.total_cmp(b));
+
+            assert_eq!(nearest.len(), 10);
+            for (found, expected) in nearest.iter().zip(expected.iter()) {
+                assert!((found.2.get::<light_year>() - expected).abs() < 1e-9);
+            }
+        }
+        assert_eq!(index.nearest(&Coordinates::new(0, 0, 0), 1000).len(), 500);
+    }
+
+    #[test]
+    fn spatial_index_sparse_queries_far_away() {
+        let mut index = SpatialIndex::new(Length::new::<light_year>(1.0), 4);
+        for (name, x) in [0, 3, -40, 250, 1_000].into_iter().enumerate() {
+            index.insert(Star {
+                coordinates: Coordinates::new(x, x / 2, -x),
+                name,
+            });
+        }
+        let far = Coordinates::new(2_000_000, -1_500_000, 3_000_000);
+
+        let nearest = index.nearest(&far, 2);
+        let names: Vec<usize> = nearest.iter().map(|(_, star, _)| star.name).collect();
+        let mut expected: Vec<(usize, f64)> = index
+            .iter()
+            .map(|(_, star)| (star.name, star.coordinates - far))
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        assert_eq!(names, vec![expected[0].0, expected[1].0]);
+        assert_eq!(index.nearest(&far, 10).len(), 5);
+
+        let everything = index.within_radius(&far, Length::new::<light_year>(1e7));
+        assert_eq!(everything.len(), 5);
+        assert!(index
+            .within_radius(&far, Length::new::<light_year>(1e5))
+            .is_empty());
+        assert_eq!(
+            index
+                .within_box(
+                    &Coordinates::new(i64::MIN, i64::MIN, i64::MIN),
+                    &Coordinates::new(300, i64::MAX, i64::MAX)
+                )
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn spatial_index_box() {
+        let index = index();
+        let found = index.within_box(
+            &Coordinates::new(10, 10, 10),
+            &Coordinates::new(-10, -10, -10),
+        );
+        let expected = index
+            .iter()
+            .filter(|(_, star)| {
+                let c = star.coordinates;
+                c.x.abs() <= 10 && c.y.abs() <= 10 && c.z.abs() <= 10
+            })
+            .count();
+        assert_eq!(found.len(), expected);
+    }
+
+    #[test]
+    fn spatial_index_insert_and_remove() {
+        let mut index = SpatialIndex::new(Length::new::<light_year>(1.0), 4);
+        let a = index.insert(Star {
+            coordinates: Coordinates::new(1, 1, 1),
+            name: 1,
+        });
+        let b = index.insert(Star {
+            coordinates: Coordinates::new(-5, 2, 9),
+            name: 2,
+        });
+        assert_eq!(index.len(), 2);
+
+        let removed = index.remove(a).unwrap();
+        assert_eq!(removed.name, 1);
+        assert!(index.remove(a).is_none());
+        assert!(index.get(a).is_none());
+        assert_eq!(index.len(), 1);
+
+        let nearest = index.nearest(&Coordinates::new(0, 0, 0), 5);
+        assert_eq!(nearest.len(), 1);
+        assert_eq!(nearest[0].0, b);
+
+        // freed slots are reused
+        let c = index.insert(Star {
+            coordinates: Coordinates::new(0, 0, 0),
+            name: 3,
+        });
+        assert_eq!(index.get(c).unwrap().name, 3);
+        assert_eq!(index.nearest(&Coordinates::new(0, 0, 0), 1)[0].0, c);
+
+        index.remove(b);
+        index.remove(c);
+        assert!(index.is_empty());
+        assert!(index.nearest(&Coordinates::new(0, 0, 0), 3).is_empty());
+    }
+}
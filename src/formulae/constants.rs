@@ -12,6 +12,7 @@ pub(crate) const MOLECULAR_CLOUD_PARTICLE_DENSITY_HIGH: f64 = 10E6 * 2000.0;
 pub(crate) const GRAVITATIONAL_CONSTANT: f64 = 6.674E-11;
 pub(crate) const GRAVITATIONAL_CONSTANT_COLLPASE_ADJUSTMENT: f64 = GRAVITATIONAL_CONSTANT * 1000.0;
 pub(crate) const BOLTZMANN_CONSTANT: f64 = 1.381E-23;
+pub(crate) const SHU_ACCRETION_COEFFICIENT: f64 = 0.975;
 
 pub(crate) const VACUUM_PERMEABILITY: f64 = 1.25663706212E-6;
 pub(crate) const GAS_CONSTANT: f64 = 8.31446261815324;
@@ -22,3 +23,5 @@ pub(crate) const PEAK_FREQUENCY_CONSTANT: f64 = 5.8789232E10;
 pub(crate) const AVOGADRO_CONSTANT: f64 = 6.02214076e23;
 
 pub(crate) const C: f64 = 2.99792458E8;
+
+pub(crate) const PLANCK_CONSTANT: f64 = 6.62607015E-34;
@@ -1,40 +1,37 @@
 #[allow(dead_code)]
-use crate::formulae::constants::{
-    BOLTZMANN_CONSTANT, GRAVITATIONAL_CONSTANT, STEFAN_BOLTZMANN_CONSTANT, ZERO_POINT_LUMINOSITY,
-};
-use crate::units::units::mass::solar_mass;
-use crate::units::units::power::solar_luminosity;
-use crate::units::units::time::million_year;
+use crate::formulae::constants::{STEFAN_BOLTZMANN_CONSTANT, ZERO_POINT_LUMINOSITY};
+use crate::wavelength::photometry;
+use crate::wavelength::photometry::Band;
 use colortemp::RGB;
-use num::integer::cbrt;
-use std::f64::consts;
-use std::f64::consts::PI;
-use uom::fmt::DisplayStyle::Abbreviation;
-use uom::num_traits::real::Real;
 use uom::num_traits::Pow;
-use uom::si::f64::{
-    Area, Length, Mass, MassDensity, Power, ThermodynamicTemperature, Time, Volume,
-};
-use uom::si::length::meter;
-use uom::si::mass::{femtogram, kilogram};
+use uom::si::f64::{Area, Length, Power, ThermodynamicTemperature};
+use uom::si::length::parsec;
 use uom::si::power::watt;
 use uom::si::thermodynamic_temperature::kelvin;
-use uom::si::time::year;
-use uom::typenum;
 
 pub mod time {
     use uom::si::{
-        f64::{MassDensity, Time},
-        time::{second, year},
+        f64::{Length, Mass, MassDensity, Power, Time},
+        time::second,
     };
 
-    use crate::formulae::constants::GRAVITATIONAL_CONSTANT_COLLPASE_ADJUSTMENT;
+    use crate::formulae::constants::{
+        GRAVITATIONAL_CONSTANT, GRAVITATIONAL_CONSTANT_COLLPASE_ADJUSTMENT,
+    };
 
     pub fn gravitational_freefall(density: MassDensity) -> Time {
         let tff: f64 =
             (1.0 / (GRAVITATIONAL_CONSTANT_COLLPASE_ADJUSTMENT * (density.value * 10.0))).sqrt();
         Time::new::<second>(tff)
     }
+
+    /// The time a star can shine by radiating away its gravitational potential energy,
+    /// `t_KH = G M² / (R L)`
+    pub fn kelvin_helmholtz(mass: Mass, radius: Length, luminosity: Power) -> Time {
+        Time::new::<second>(
+            (GRAVITATIONAL_CONSTANT * mass.value.powi(2)) / (radius.value * luminosity.value),
+        )
+    }
 }
 
 pub mod energy {
@@ -69,12 +66,10 @@ pub mod moles {
 
 pub mod wavelength {
     use uom::fmt::DisplayStyle::Abbreviation;
-    use uom::si::frequency::{centihertz, decihertz, gigahertz, hectohertz, kilohertz};
-    use uom::si::length::centimeter;
+    use uom::si::frequency::{gigahertz, kilohertz};
     use uom::si::{
         f64::{Frequency, Length, ThermodynamicTemperature},
-        frequency::hertz,
-        length::{meter, nanometer},
+        length::meter,
     };
 
     use crate::formulae::constants::PEAK_FREQUENCY_CONSTANT;
@@ -126,12 +121,9 @@ pub mod mass {
 
 pub mod force {
     use num::traits::Pow;
-    use uom::{
-        si::{
-            f64::{Force, Length, Mass},
-            force::newton,
-        },
-        typenum::P2,
+    use uom::si::{
+        f64::{Force, Length, Mass},
+        force::newton,
     };
 
     use crate::formulae::constants::GRAVITATIONAL_CONSTANT;
@@ -165,16 +157,28 @@ pub mod length {
     use num::traits::Pow;
     use std::f64::consts::PI;
     use uom::si::f64::{Length, Mass, MassDensity, ThermodynamicTemperature, Volume};
-    use uom::si::length::meter;
+    use uom::si::length::{meter, parsec};
 
     use crate::formulae::constants::{BOLTZMANN_CONSTANT, GRAVITATIONAL_CONSTANT};
-    use crate::formulae::formulae::energy;
-    use crate::units::units::mass::dalton;
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::{dalton, solar_mass};
 
     pub fn sphere_radius_from_volume(volume: Volume) -> Length {
         Length::new::<meter>(f64::cbrt(3.0 * volume.value / (4.0 * PI)))
     }
 
+    /// The distance to an object from its distance modulus, `m - M`
+    pub fn from_distance_modulus(modulus: f64) -> Length {
+        Length::new::<parsec>(10.0 * 10.0_f64.powf(modulus / 5.0))
+    }
+
+    /// The approximate radius of a star of the given mass when it joins the main sequence
+    pub fn main_sequence_radius(mass: Mass) -> Length {
+        let solar_masses = mass.get::<solar_mass>();
+        let exponent = if solar_masses < 1.0 { 0.8 } else { 0.57 };
+        Length::new::<solar_radius>(solar_masses.powf(exponent))
+    }
+
     pub fn jeans_radius(
         mass: Mass,
         radius: Length,
@@ -197,8 +201,6 @@ pub mod density {
     use crate::formulae::constants::GAS_CONSTANT;
     use uom::si::f64::{Mass, MassDensity, MolarMass, Pressure, ThermodynamicTemperature, Volume};
     use uom::si::mass_density::kilogram_per_cubic_meter;
-    use uom::si::molar_mass::gram_per_mole;
-    use uom::si::pressure::atmosphere;
 
     pub fn from_mass_and_volume(mass: Mass, volume: Volume) -> MassDensity {
         mass / volume
@@ -215,17 +217,84 @@ pub mod density {
     }
 }
 
+pub mod velocity {
+    use uom::si::f64::{Mass, ThermodynamicTemperature, Velocity};
+    use uom::si::velocity::meter_per_second;
+
+    use crate::formulae::constants::BOLTZMANN_CONSTANT;
+    use crate::units::units::mass::dalton;
+
+    /// The isothermal sound speed of a gas, `c_s = sqrt(kT / μ m_u)`
+    pub fn isothermal_sound_speed(
+        temperature: ThermodynamicTemperature,
+        mean_molecular_weight: f64,
+    ) -> Velocity {
+        Velocity::new::<meter_per_second>(f64::sqrt(
+            (BOLTZMANN_CONSTANT * temperature.value)
+                / (mean_molecular_weight * Mass::new::<dalton>(1.0).value),
+        ))
+    }
+}
+
+pub mod mass_rate {
+    use uom::si::f64::{MassRate, Velocity};
+    use uom::si::mass_rate::kilogram_per_second;
+
+    use crate::formulae::constants::{GRAVITATIONAL_CONSTANT, SHU_ACCRETION_COEFFICIENT};
+
+    /// The rate at which a collapsing isothermal sphere feeds its protostar, `Ṁ ≈ 0.975 c_s³ / G`.
+    /// Derived from the inside-out collapse solution of Shu (1977).
+    pub fn accretion_from_sound_speed(sound_speed: Velocity) -> MassRate {
+        MassRate::new::<kilogram_per_second>(
+            SHU_ACCRETION_COEFFICIENT * sound_speed.value.powi(3) / GRAVITATIONAL_CONSTANT,
+        )
+    }
+}
+
 pub mod power {
-    use uom::si::f64::{Energy, Length, Mass, Power};
+    use std::f64::consts::PI;
+    use uom::si::f64::{Energy, Length, Mass, MassRate, Power};
+    use uom::si::power::watt;
+
+    use crate::formulae::constants::GRAVITATIONAL_CONSTANT;
+    use crate::formulae::formulae::volume;
+    use crate::units::units::mass::solar_mass;
+    use crate::units::units::power::solar_luminosity;
 
+    /// The power radiated by a sphere collapsing under its own gravity.
+    /// By the virial theorem half of the released potential energy is radiated away, which we
+    /// spread over the free-fall time of the sphere.
     pub fn from_gravitational_collapse(
         potential_energy: Energy,
         mass: Mass,
         radius: Length,
     ) -> Power {
-        //todo needs jeans radius
-        // (3.0/10.0) * ((GRAVITATIONAL_CONSTANT * mass).pow(2.0) / radius.value.pow(2.0)) * ()
-        todo!()
+        let density = mass.value / volume::sphere_volume_from_length(radius).value;
+        let freefall = f64::sqrt((3.0 * PI) / (32.0 * GRAVITATIONAL_CONSTANT * density));
+        Power::new::<watt>(0.5 * potential_energy.value.abs() / freefall)
+    }
+
+    /// The approximate luminosity of a star of the given mass when it joins the main sequence,
+    /// from the piecewise mass-luminosity relation
+    pub fn main_sequence_luminosity(mass: Mass) -> Power {
+        let solar_masses = mass.get::<solar_mass>();
+        let luminosity = if solar_masses < 0.43 {
+            0.23 * solar_masses.powf(2.3)
+        } else if solar_masses < 2.0 {
+            solar_masses.powi(4)
+        } else if solar_masses < 55.0 {
+            1.4 * solar_masses.powf(3.5)
+        } else {
+            32000.0 * solar_masses
+        };
+        Power::new::<solar_luminosity>(luminosity)
+    }
+
+    /// The luminosity released by material falling onto the surface of a protostar, `L = G M Ṁ / R`
+    pub fn from_accretion(mass: Mass, accretion_rate: MassRate, radius: Length) -> Power {
+        Power::new::<watt>(
+            GRAVITATIONAL_CONSTANT * mass.value * accretion_rate.value / radius.value,
+        )
     }
 }
 
@@ -246,8 +315,10 @@ pub fn calculate_colour(temperature: ThermodynamicTemperature) -> RGB {
     colortemp::temp_to_rgb(temperature.value as i64)
 }
 
+/// The B-V colour index of a black body, from synthetic photometry of its Planck spectrum
+/// through the Johnson B and V filters.
 pub fn calculate_bv_index(temperature: ThermodynamicTemperature) -> f64 {
-    (5601.0 / temperature.value).powf(1.5) - 0.4
+    photometry::colour_index(temperature, &Band::B, &Band::V)
 }
 
 pub fn bv_to_rgb(bv: f64) -> RGB {
@@ -321,6 +392,17 @@ pub fn calculate_absolute_magnitude(luminosity: Power) -> f64 {
     -2.5 * (f64::log10(luminosity.value / ZERO_POINT_LUMINOSITY))
 }
 
+/// The difference between the apparent and absolute magnitude of an object at a given distance,
+/// `m - M = 5 log₁₀(d / 10 pc)`
+pub fn calculate_distance_modulus(distance: Length) -> f64 {
+    5.0 * f64::log10(distance.get::<parsec>() / 10.0)
+}
+
+/// The bolometric magnitude of an object as seen from a given distance
+pub fn calculate_apparent_magnitude(luminosity: Power, distance: Length) -> f64 {
+    calculate_absolute_magnitude(luminosity) + calculate_distance_modulus(distance)
+}
+
 // pub fn stellar_lifespan(mass: Mass, randomness: Option<&mut Randomness>) -> Time {
 //     let calc = Time::new::<year>(
 //         f64::pow(10.0, 10.0) * f64::pow((Mass::new::<solar_mass>(1.0) / mass).value, 2.5),
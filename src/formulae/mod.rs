@@ -1,3 +1,5 @@
 pub mod constants;
 pub mod formulae;
 mod formulae_test;
+pub mod polytrope;
+mod polytrope_test;
@@ -0,0 +1,232 @@
+use std::f64::consts::PI;
+use uom::si::f64::{Length, Mass, MassDensity, Pressure, ThermodynamicTemperature};
+use uom::si::length::meter;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::pascal;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::formulae::constants::{BOLTZMANN_CONSTANT, GRAVITATIONAL_CONSTANT};
+use crate::formulae::formulae::density;
+use crate::formulae::formulae::volume;
+use crate::units::units::mass::dalton;
+
+/// The default step size used when integrating the Lane-Emden equation.
+pub const DEFAULT_STEP: f64 = 1.0e-3;
+
+/// The furthest dimensionless radius the solver will integrate to before giving up on finding a
+/// surface. Polytropes with an index of 5 or more never reach a surface.
+const MAXIMUM_XI: f64 = 1.0e3;
+
+/// A numerical solution to the Lane-Emden equation for a polytrope of index `n`.
+///
+/// The solution is dimensionless: `xi` is the scaled radius and `theta` the scaled density such
+/// that `ρ = ρc θⁿ`. Samples are stored in ascending `xi`, starting at the centre.
+#[derive(Debug, Clone)]
+pub struct LaneEmden {
+    pub index: f64,
+    pub xi: Vec<f64>,
+    pub theta: Vec<f64>,
+    pub dtheta: Vec<f64>,
+
+    /// The first root of θ, i.e. the surface of the polytrope. `None` when no surface exists.
+    pub first_zero: Option<f64>,
+    /// dθ/dξ evaluated at the surface.
+    pub surface_derivative: Option<f64>,
+}
+
+impl LaneEmden {
+    /// Integrate the Lane-Emden equation for polytropic index `index` with a fourth order
+    /// Runge-Kutta scheme, stopping at the first root of θ.
+    ///
+    /// The integration starts slightly off-centre using the series expansion of θ around ξ = 0,
+    /// which avoids the singular `2/ξ` term.
+    ///
+    /// Returns `None` if `step` is not positive and finite, as the integration would never end.
+    pub fn solve(index: f64, step: f64) -> Option<LaneEmden> {
+        if !(step.is_finite() && step > 0.0) {
+            return None;
+        }
+
+        let series = |xi: f64| {
+            (
+                1.0 - xi.powi(2) / 6.0 + index * xi.powi(4) / 120.0,
+                -xi / 3.0 + index * xi.powi(3) / 30.0,
+            )
+        };
+
+        let mut xi = step;
+        let (mut theta, mut dtheta) = series(xi);
+        let mut solution = LaneEmden {
+            index,
+            xi: vec![0.0, xi],
+            theta: vec![1.0, theta],
+            dtheta: vec![0.0, dtheta],
+            first_zero: None,
+            surface_derivative: None,
+        };
+
+        while xi < MAXIMUM_XI {
+            let (next_theta, next_dtheta) = LaneEmden::step(index, xi, theta, dtheta, step);
+
+            if next_theta <= 0.0 {
+                // linearly interpolate across the step to find where θ crosses zero
+                let fraction = theta / (theta - next_theta);
+                let zero = xi + step * fraction;
+                let derivative = dtheta + (next_dtheta - dtheta) * fraction;
+                solution.xi.push(zero);
+                solution.theta.push(0.0);
+                solution.dtheta.push(derivative);
+                solution.first_zero = Some(zero);
+                solution.surface_derivative = Some(derivative);
+                break;
+            }
+
+            xi += step;
+            theta = next_theta;
+            dtheta = next_dtheta;
+            solution.xi.push(xi);
+            solution.theta.push(theta);
+            solution.dtheta.push(dtheta);
+        }
+
+        Some(solution)
+    }
+
+    fn step(index: f64, xi: f64, theta: f64, dtheta: f64, h: f64) -> (f64, f64) {
+        // θ'' = -θⁿ - 2θ'/ξ, with θ clamped at zero so fractional indices stay real
+        let derivative =
+            |x: f64, t: f64, dt: f64| -> f64 { -t.max(0.0).powf(index) - 2.0 * dt / x };
+
+        let k1_t = dtheta;
+        let k1_dt = derivative(xi, theta, dtheta);
+        let k2_t = dtheta + 0.5 * h * k1_dt;
+        let k2_dt = derivative(
+            xi + 0.5 * h,
+            theta + 0.5 * h * k1_t,
+            dtheta + 0.5 * h * k1_dt,
+        );
+        let k3_t = dtheta + 0.5 * h * k2_dt;
+        let k3_dt = derivative(
+            xi + 0.5 * h,
+            theta + 0.5 * h * k2_t,
+            dtheta + 0.5 * h * k2_dt,
+        );
+        let k4_t = dtheta + h * k3_dt;
+        let k4_dt = derivative(xi + h, theta + h * k3_t, dtheta + h * k3_dt);
+
+        (
+            theta + h / 6.0 * (k1_t + 2.0 * k2_t + 2.0 * k3_t + k4_t),
+            dtheta + h / 6.0 * (k1_dt + 2.0 * k2_dt + 2.0 * k3_dt + k4_dt),
+        )
+    }
+
+    /// Sample θ at an arbitrary dimensionless radius by linear interpolation between steps.
+    /// Beyond the surface θ is zero.
+    pub fn theta_at(&self, xi: f64) -> f64 {
+        if xi <= 0.0 {
+            return 1.0;
+        }
+        let upper = self.xi.partition_point(|sample| *sample < xi);
+        if upper >= self.xi.len() {
+            return match self.first_zero {
+                Some(_) => 0.0,
+                None => *self.theta.last().unwrap_or(&0.0),
+            };
+        }
+        let (x0, x1) = (self.xi[upper - 1], self.xi[upper]);
+        let (t0, t1) = (self.theta[upper - 1], self.theta[upper]);
+        t0 + (t1 - t0) * (xi - x0) / (x1 - x0)
+    }
+
+    /// The ratio of central density to mean density, ρc/ρ̄ = -ξ₁ / (3θ'(ξ₁)).
+    pub fn central_concentration(&self) -> Option<f64> {
+        match (self.first_zero, self.surface_derivative) {
+            (Some(xi), Some(dtheta)) => Some(-xi / (3.0 * dtheta)),
+            _ => None,
+        }
+    }
+}
+
+/// A star of a given mass and radius modelled as a polytrope, `P = K ρ^(1 + 1/n)`.
+#[derive(Debug, Clone)]
+pub struct Polytrope {
+    pub solution: LaneEmden,
+
+    pub mass: Mass,
+    pub radius: Length,
+    /// The length that converts the dimensionless radius ξ into a physical radius, `r = α ξ`.
+    pub scale_length: Length,
+
+    pub central_density: MassDensity,
+    pub central_pressure: Pressure,
+    pub central_temperature: ThermodynamicTemperature,
+
+    pub mean_molecular_weight: f64,
+}
+
+impl Polytrope {
+    /// Build a polytrope of index `index` scaled to the given mass and radius.
+    /// The central temperature assumes an ideal gas with the given mean molecular weight.
+    ///
+    /// Returns `None` for indices of 5 or more, which have no finite radius.
+    pub fn new(
+        index: f64,
+        mass: Mass,
+        radius: Length,
+        mean_molecular_weight: f64,
+    ) -> Option<Polytrope> {
+        let solution = LaneEmden::solve(index, DEFAULT_STEP)?;
+        let first_zero = solution.first_zero?;
+        let concentration = solution.central_concentration()?;
+
+        let scale_length = Length::new::<meter>(radius.value / first_zero);
+        let mean_density =
+            density::from_mass_and_volume(mass, volume::sphere_volume_from_length(radius));
+        let central_density =
+            MassDensity::new::<kilogram_per_cubic_meter>(mean_density.value * concentration);
+        let central_pressure = Pressure::new::<pascal>(
+            4.0 * PI
+                * GRAVITATIONAL_CONSTANT
+                * scale_length.value.powi(2)
+                * central_density.value.powi(2)
+                / (index + 1.0),
+        );
+        let central_temperature = ThermodynamicTemperature::new::<kelvin>(
+            central_pressure.value * mean_molecular_weight * Mass::new::<dalton>(1.0).value
+                / (BOLTZMANN_CONSTANT * central_density.value),
+        );
+
+        Some(Polytrope {
+            solution,
+            mass,
+            radius,
+            scale_length,
+            central_density,
+            central_pressure,
+            central_temperature,
+            mean_molecular_weight,
+        })
+    }
+
+    fn theta_at(&self, radius: Length) -> f64 {
+        self.solution
+            .theta_at(radius.value / self.scale_length.value)
+    }
+
+    /// The density at a given distance from the centre
+    pub fn density_at(&self, radius: Length) -> MassDensity {
+        self.central_density * self.theta_at(radius).powf(self.solution.index)
+    }
+
+    /// The pressure at a given distance from the centre
+    pub fn pressure_at(&self, radius: Length) -> Pressure {
+        self.central_pressure * self.theta_at(radius).powf(self.solution.index + 1.0)
+    }
+
+    /// The temperature at a given distance from the centre
+    pub fn temperature_at(&self, radius: Length) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(
+            self.central_temperature.value * self.theta_at(radius),
+        )
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use crate::formulae::polytrope::{LaneEmden, Polytrope, DEFAULT_STEP};
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::solar_mass;
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::PI;
+    use uom::si::f64::{Length, Mass};
+    use uom::si::mass_density::kilogram_per_cubic_meter;
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    #[test]
+    fn lane_emden_analytic_solutions() {
+        // n = 0 and n = 1 have closed form solutions with roots at √6 and π
+        let n0 = LaneEmden::solve(0.0, DEFAULT_STEP).unwrap();
+        assert_approx_eq!(f64, n0.first_zero.unwrap(), 6.0_f64.sqrt(), epsilon = 1e-4);
+        assert_approx_eq!(f64, n0.theta_at(1.0), 1.0 - 1.0 / 6.0, epsilon = 1e-6);
+
+        let n1 = LaneEmden::solve(1.0, DEFAULT_STEP).unwrap();
+        assert_approx_eq!(f64, n1.first_zero.unwrap(), PI, epsilon = 1e-4);
+        assert_approx_eq!(f64, n1.theta_at(2.0), 2.0_f64.sin() / 2.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn lane_emden_tabulated_solutions() {
+        let n15 = LaneEmden::solve(1.5, DEFAULT_STEP).unwrap();
+        assert_approx_eq!(f64, n15.first_zero.unwrap(), 3.65375, epsilon = 1e-3);
+        assert_approx_eq!(
+            f64,
+            n15.surface_derivative.unwrap(),
+            -0.20330,
+            epsilon = 1e-3
+        );
+
+        let n3 = LaneEmden::solve(3.0, DEFAULT_STEP).unwrap();
+        assert_approx_eq!(f64, n3.first_zero.unwrap(), 6.89685, epsilon = 1e-3);
+        assert_approx_eq!(
+            f64,
+            n3.central_concentration().unwrap(),
+            54.18,
+            epsilon = 0.1
+        );
+    }
+
+    #[test]
+    fn lane_emden_rejects_steps_that_never_advance() {
+        for step in [0.0, -DEFAULT_STEP, f64::NAN, f64::INFINITY] {
+            assert!(LaneEmden::solve(1.5, step).is_none());
+        }
+    }
+
+    #[test]
+    fn lane_emden_without_surface() {
+        let n5 = LaneEmden::solve(5.0, 0.1).unwrap();
+        assert!(n5.first_zero.is_none());
+        assert!(n5.central_concentration().is_none());
+        assert!(Polytrope::new(
+            5.0,
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(1.0),
+            0.6
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn polytrope_sun_eddington_model() {
+        // the Eddington standard model treats the sun as an n = 3 polytrope
+        let sun = Polytrope::new(
+            3.0,
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(1.0),
+            0.61,
+        )
+        .unwrap();
+
+        let central_density = sun.central_density.get::<kilogram_per_cubic_meter>();
+        assert!(central_density > 7.5e4 && central_density < 7.8e4);
+
+        let central_temperature = sun.central_temperature.get::<kelvin>();
+        assert!(central_temperature > 1.1e7 && central_temperature < 1.3e7);
+
+        assert_eq!(
+            sun.density_at(Length::new::<solar_radius>(0.0)),
+            sun.central_density
+        );
+        assert_eq!(sun.density_at(Length::new::<solar_radius>(1.5)).value, 0.0);
+        assert!(sun.pressure_at(Length::new::<solar_radius>(0.5)) < sun.central_pressure);
+        assert!(sun.temperature_at(Length::new::<solar_radius>(0.5)) < sun.central_temperature);
+    }
+}
@@ -0,0 +1,243 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use uom::si::f64::{Length, Mass};
+use uom::si::length::{light_year, parsec};
+
+use crate::chemistry::elements::elements::Element;
+use crate::chemistry::elements::silicon::SiliconIsotope;
+use crate::chemistry::molecules::molecules::Molecule;
+use crate::cloud::cloud::{CloudOptions, MolecularCloud};
+use crate::cloud::density_field::DensityFieldOptions;
+use crate::coordinates::coordinates::{Cartesian, Coordinates};
+use crate::coordinates::position::GalacticPosition;
+use crate::gas::gas::UniformGas;
+use crate::units::units::mass::solar_mass;
+
+/// The shape of a disk galaxy and how likely each sector of it is to hold something.
+/// The galactic centre sits at the origin with the disk in the x-y plane.
+pub struct GalaxyModel {
+    /// The width of each cubic sector addressed by a `Coordinates` cell
+    pub sector_size: Length,
+    /// The radius over which the disk density falls by a factor of e
+    pub disk_scale_length: Length,
+    /// The height above the midplane over which the disk density falls by a factor of e
+    pub disk_scale_height: Length,
+    pub spiral_arms: usize,
+    /// The angle between the spiral arms and a circle around the centre, in radians
+    pub pitch_angle: f64,
+    /// The width of the gaussian density profile across each arm
+    pub arm_width: Length,
+    /// How much denser the centre of an arm is than the disk around it
+    pub arm_contrast: f64,
+    /// The chance a sector at the galactic centre holds a molecular cloud
+    pub cloud_probability: f64,
+    /// The chance a sector at the galactic centre holds a star cluster
+    pub cluster_probability: f64,
+}
+
+impl GalaxyModel {
+    /// A model of the molecular disk of the Milky Way.
+    pub fn milky_way(sector_size: Length) -> GalaxyModel {
+        GalaxyModel {
+            sector_size,
+            disk_scale_length: Length::new::<parsec>(3000.0),
+            disk_scale_height: Length::new::<parsec>(75.0),
+            spiral_arms: 4,
+            pitch_angle: 12.0_f64.to_radians(),
+            arm_width: Length::new::<parsec>(300.0),
+            arm_contrast: 3.0,
+            cloud_probability: 0.1,
+            cluster_probability: 0.02,
+        }
+    }
+
+    /// The density of the galaxy at a point relative to the smooth disk at the galactic centre.
+    /// Spiral arms raise the density above the smooth disk by up to `arm_contrast`.
+    pub fn relative_density(&self, x: Length, y: Length, z: Length) -> f64 {
+        let radius = (x * x + y * y).sqrt();
+        let disk = (-radius.value / self.disk_scale_length.value).exp()
+            * (-z.value.abs() / self.disk_scale_height.value).exp();
+        disk * (1.0 + self.arm_contrast * self.arm_profile(x, y))
+    }
+
+    /// How close a point in the plane is to the centre of a spiral arm, from one on an arm to
+    /// zero far between arms.
+    pub fn arm_profile(&self, x: Length, y: Length) -> f64 {
+        let radius = (x * x + y * y).sqrt().value;
+        if self.spiral_arms == 0 || radius == 0.0 {
+            return 0.0;
+        }
+        // each arm is a logarithmic spiral, θ = ln(R / h) / tan(pitch) + 2πi / arms
+        let separation = 2.0 * PI / self.spiral_arms as f64;
+        let arm_angle = (radius / self.disk_scale_length.value).ln() / self.pitch_angle.tan();
+        let offset = (y.value.atan2(x.value) - arm_angle).rem_euclid(separation);
+        let offset = offset.min(separation - offset);
+
+        // the perpendicular distance to the arm, rather than the distance around the circle
+        let distance = radius * offset * self.pitch_angle.sin();
+        (-distance.powi(2) / (2.0 * self.arm_width.value.powi(2))).exp()
+    }
+}
+
+/// A gravitationally bound group of stars.
+#[derive(Debug)]
+pub struct StarCluster {
+    pub coordinates: Coordinates,
+    pub mass: Mass,
+    pub radius: Length,
+}
+
+impl Cartesian for StarCluster {
+    fn coordinates(&self) -> &Coordinates {
+        &self.coordinates
+    }
+}
+
+/// What kind of object a sector holds, known without generating it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectorKind {
+    Empty,
+    MolecularCloud,
+    StarCluster,
+}
+
+#[derive(Debug)]
+pub enum SectorContents {
+    /// Nothing but the diffuse interstellar medium
+    Empty,
+    MolecularCloud(MolecularCloud),
+    StarCluster(StarCluster),
+}
+
+#[derive(Debug)]
+pub struct Sector {
+    pub coordinates: Coordinates,
+    /// The density of the galaxy at the sector relative to the galactic centre
+    pub relative_density: f64,
+    pub contents: SectorContents,
+}
+
+impl Cartesian for Sector {
+    fn coordinates(&self) -> &Coordinates {
+        &self.coordinates
+    }
+}
+
+/// A procedurally generated galaxy, divided into sectors that are generated on demand.
+/// The same sector always generates the same contents.
+pub struct Galaxy {
+    pub model: GalaxyModel,
+}
+
+impl Galaxy {
+    pub fn new(model: GalaxyModel) -> Galaxy {
+        Galaxy { model }
+    }
+
+    /// The position of the centre of a sector relative to the galactic centre
+    pub fn sector_centre(&self, coordinates: &Coordinates) -> GalacticPosition {
+        coordinates.position(self.model.sector_size)
+    }
+
+    pub fn relative_density(&self, coordinates: &Coordinates) -> f64 {
+        let GalacticPosition(centre) = self.sector_centre(coordinates);
+        self.model.relative_density(centre.x, centre.y, centre.z)
+    }
+
+    /// Decide what a sector holds, without generating it.
+    pub fn kind(&self, coordinates: &Coordinates) -> SectorKind {
+        let mut rng = coordinates.seed().derive("sector").rng();
+        let density = self.relative_density(coordinates);
+        let cloud_chance = (self.model.cloud_probability * density).min(1.0);
+        let cluster_chance = (self.model.cluster_probability * density).min(1.0 - cloud_chance);
+
+        let roll: f64 = rng.gen_range(0.0..1.0);
+        if roll < cloud_chance {
+            SectorKind::MolecularCloud
+        } else if roll < cloud_chance + cluster_chance {
+            SectorKind::StarCluster
+        } else {
+            SectorKind::Empty
+        }
+    }
+
+    /// Generate the contents of a sector.
+    pub fn sector(&self, coordinates: Coordinates) -> Sector {
+        let mut rng = coordinates.seed().derive("sector").derive("contents").rng();
+        let contents = match self.kind(&coordinates) {
+            SectorKind::Empty => SectorContents::Empty,
+            SectorKind::MolecularCloud => {
+                SectorContents::MolecularCloud(Galaxy::generate_cloud(coordinates, &mut rng))
+            }
+            SectorKind::StarCluster => {
+                SectorContents::StarCluster(Galaxy::generate_cluster(coordinates, &mut rng))
+            }
+        };
+        Sector {
+            coordinates,
+            relative_density: self.relative_density(&coordinates),
+            contents,
+        }
+    }
+
+    /// Lazily generate every sector within `distance` cells of `centre`, along each axis.
+    pub fn sectors_around<'a>(
+        &'a self,
+        centre: Coordinates,
+        distance: i64,
+    ) -> impl Iterator<Item = Sector> + 'a {
+        let range = move |axis: i64| (axis - distance)..=(axis + distance);
+        range(centre.x).flat_map(move |x| {
+            range(centre.y).flat_map(move |y| {
+                range(centre.z).map(move |z| self.sector(Coordinates::new(x, y, z)))
+            })
+        })
+    }
+
+    fn generate_cloud(coordinates: Coordinates, rng: &mut ChaCha20Rng) -> MolecularCloud {
+        let radius = Length::new::<light_year>(rng.gen_range(5.0..50.0));
+        let molecules_per_cubic_meter = rng.gen_range(1e8..1e9);
+        MolecularCloud::new(
+            coordinates,
+            radius,
+            UniformGas::generate_massdensity(
+                &Molecule::molecular_hydrogen(),
+                molecules_per_cubic_meter,
+            ),
+            vec![
+                (Molecule::molecular_hydrogen(), 70.0),
+                (Molecule::atomic_helium(), 27.0),
+                (Molecule::carbon_monoxide(), 2.0),
+                (
+                    Molecule::new(vec![(Element::Silicon(SiliconIsotope::Silicon), 1)]),
+                    1.0,
+                ),
+            ],
+            CloudOptions {
+                use_randomness: true,
+                core_formation_chance: 80.0,
+                core_formation_dropoff: 0.8,
+                turbulence: Some(DensityFieldOptions {
+                    mach_number: rng.gen_range(5.0..20.0),
+                    forcing: 0.4,
+                    spectral_index: 11.0 / 3.0,
+                    modes: 64,
+                }),
+            },
+        )
+    }
+
+    fn generate_cluster(coordinates: Coordinates, rng: &mut ChaCha20Rng) -> StarCluster {
+        // sample the cluster mass function, dN/dM ∝ M⁻², between 100 and 100,000 solar masses
+        let (lower, upper) = (100.0_f64, 100_000.0_f64);
+        let fraction: f64 = rng.gen_range(0.0..1.0);
+        let mass = 1.0 / (1.0 / lower - fraction * (1.0 / lower - 1.0 / upper));
+        StarCluster {
+            coordinates,
+            mass: Mass::new::<solar_mass>(mass),
+            radius: Length::new::<parsec>(rng.gen_range(1.0..10.0)),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::galaxy::galaxy::{Galaxy, GalaxyModel, SectorContents, SectorKind};
+    use crate::Coordinates;
+    use uom::si::f64::Length;
+    use uom::si::length::{light_year, parsec};
+
+    fn galaxy() -> Galaxy {
+        Galaxy::new(GalaxyModel::milky_way(Length::new::<light_year>(100.0)))
+    }
+
+    #[test]
+    fn galaxy_density_falls_with_radius_and_height() {
+        let model = GalaxyModel::milky_way(Length::new::<light_year>(100.0));
+        let pc = Length::new::<parsec>;
+
+        let solar = model.relative_density(pc(8000.0), pc(0.0), pc(0.0));
+        let outer = model.relative_density(pc(12000.0), pc(0.0), pc(0.0));
+        let above = model.relative_density(pc(8000.0), pc(0.0), pc(300.0));
+        assert!(solar > 0.0);
+        assert!(outer < solar);
+        // four scale heights above the plane is e⁴ less dense
+        assert!((solar / above - 4.0_f64.exp()).abs() < 1e-9);
+        assert_eq!(
+            model.relative_density(pc(8000.0), pc(0.0), pc(-300.0)),
+            above
+        );
+    }
+
+    #[test]
+    fn galaxy_spiral_arms_are_overdense() {
+        let model = GalaxyModel::milky_way(Length::new::<light_year>(100.0));
+        let radius = 8000.0;
+
+        // walk around a circle and compare the densest and sparsest points
+        let profile: Vec<f64> = (0..360)
+            .map(|degree| {
+                let angle = (degree as f64).to_radians();
+                model.arm_profile(
+                    Length::new::<parsec>(radius * angle.cos()),
+                    Length::new::<parsec>(radius * angle.sin()),
+                )
+            })
+            .collect();
+        let peak = profile.iter().cloned().fold(0.0, f64::max);
+        let trough = profile.iter().cloned().fold(1.0, f64::min);
+        assert!(peak > 0.95);
+        assert!(trough < 0.05);
+
+        // one crossing into an arm for each arm
+        let crossings = (0..360)
+            .filter(|i| profile[*i] < 0.5 && profile[(i + 1) % 360] >= 0.5)
+            .count();
+        assert_eq!(crossings, model.spiral_arms);
+    }
+
+    #[test]
+    fn galaxy_sectors_are_deterministic() {
+        let galaxy = galaxy();
+        for x in 0..20 {
+            let coordinates = Coordinates::new(260 + x, 3, 0);
+            assert_eq!(galaxy.kind(&coordinates), galaxy.kind(&coordinates));
+        }
+
+        let cloud = (0..200)
+            .map(|x| Coordinates::new(x, 0, 0))
+            .find(|coordinates| galaxy.kind(coordinates) == SectorKind::MolecularCloud)
+            .unwrap();
+        let first = galaxy.sector(cloud);
+        let second = galaxy.sector(cloud);
+        match (first.contents, second.contents) {
+            (SectorContents::MolecularCloud(a), SectorContents::MolecularCloud(b)) => {
+                assert_eq!(a.radius, b.radius);
+                assert_eq!(a.mass, b.mass);
+            }
+            _ => panic!("expected a molecular cloud in both sectors"),
+        }
+    }
+
+    #[test]
+    fn galaxy_objects_crowd_the_midplane() {
+        let galaxy = galaxy();
+        let count = |z: i64| {
+            (0..2000)
+                .filter(|x| galaxy.kind(&Coordinates::new(200 + x, 0, z)) != SectorKind::Empty)
+                .count()
+        };
+        // a sector is 100 light years, about 30 parsecs, so ten sectors up is four scale heights
+        assert!(count(0) > 5 * count(10));
+        assert!(count(0) > 0);
+    }
+
+    #[test]
+    fn galaxy_sectors_are_generated_lazily() {
+        let galaxy = galaxy();
+        let centre = Coordinates::new(270, 0, 40);
+        let mut sectors = galaxy.sectors_around(centre, 2);
+        assert_eq!(
+            sectors.next().unwrap().coordinates,
+            Coordinates::new(268, -2, 38)
+        );
+        assert_eq!(galaxy.sectors_around(centre, 2).count(), 125);
+        assert!(galaxy
+            .sectors_around(centre, 1)
+            .all(|sector| sector.relative_density > 0.0));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod galaxy;
+mod galaxy_test;
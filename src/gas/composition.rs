@@ -0,0 +1,465 @@
+use crate::chemistry::elements::elements::Element;
+use crate::chemistry::molecules::molecules::Molecule;
+use crate::formulae::constants::GAS_CONSTANT;
+use crate::transition::transition::{
+    EasingFunction, Interpolatable, InterpolationSpace, Interpolationf64AsyncOptions,
+};
+use crate::units::units::mass::dalton;
+use std::collections::HashMap;
+use uom::si::f64::{Mass, MolarHeatCapacity, MolarMass};
+use uom::si::molar_heat_capacity::joule_per_kelvin_mole;
+use uom::si::molar_mass::gram_per_mole;
+
+/// The molecules that make up a gas, each with its percentage of the molecules by number.
+///
+/// Building a composition with `new` merges duplicate molecules and scales the percentages to
+/// sum to 100. Compositions written out by hand are trusted as they are, and one with nothing
+/// in it has no fractions, a mean molecular weight of zero and no heat capacity.
+#[derive(Debug, Clone)]
+pub struct Composition(pub Vec<(Molecule, f64)>);
+
+/// The mass fractions of hydrogen (X), helium (Y) and everything heavier (Z), which sum to one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metallicity {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// The present-day solar photosphere's mass fractions (Asplund et al. 2009)
+pub const SOLAR_METALLICITY: Metallicity = Metallicity {
+    x: 0.7381,
+    y: 0.2485,
+    z: 0.0134,
+};
+
+/// Present-day solar photospheric abundances (Asplund et al. 2009), as log ε, the base 10
+/// logarithm of the number of atoms per 10¹² hydrogen atoms.
+pub const SOLAR_ABUNDANCES: [(&str, f64); 14] = [
+    ("H", 12.00),
+    ("He", 10.93),
+    ("C", 8.43),
+    ("N", 7.83),
+    ("O", 8.69),
+    ("Ne", 7.93),
+    ("Na", 6.24),
+    ("Mg", 7.60),
+    ("Al", 6.45),
+    ("Si", 7.51),
+    ("S", 7.12),
+    ("Ar", 6.40),
+    ("Ca", 6.34),
+    ("Fe", 7.50),
+];
+
+/// How much of a gas is made of one element, counting every isotope of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementalAbundance {
+    pub symbol: &'static str,
+    /// The fraction of all atoms that are this element
+    pub number_fraction: f64,
+    /// The fraction of the gas' mass that is this element
+    pub mass_fraction: f64,
+}
+
+/// How much of an element is made of one of its isotopes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsotopicAbundance {
+    pub isotope: Element,
+    /// The fraction of this element's atoms that are this isotope
+    pub number_fraction: f64,
+    /// The fraction of this element's mass that is this isotope
+    pub mass_fraction: f64,
+}
+
+impl Composition {
+    /// A composition from relative amounts by number, which need not sum to 100.
+    /// Returns `None` if there is nothing to make a composition from, or an amount is negative.
+    pub fn new(species: Vec<(Molecule, f64)>) -> Option<Composition> {
+        if species
+            .iter()
+            .any(|(_, amount)| *amount < 0.0 || !amount.is_finite())
+        {
+            return None;
+        }
+        let mut merged: Vec<(Molecule, f64)> = vec![];
+        for (molecule, amount) in species {
+            match merged.iter_mut().find(|(other, _)| *other == molecule) {
+                Some((_, total)) => *total += amount,
+                None => merged.push((molecule, amount)),
+            }
+        }
+        merged.retain(|(_, amount)| *amount > 0.0);
+
+        let total: f64 = merged.iter().map(|(_, amount)| amount).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        Some(Composition(
+            merged
+                .into_iter()
+                .map(|(molecule, amount)| (molecule, amount / total * 100.0))
+                .collect(),
+        ))
+    }
+
+    /// A composition from relative amounts by mass, converted to percentages by number
+    pub fn from_mass_fractions(species: Vec<(Molecule, f64)>) -> Option<Composition> {
+        Composition::new(
+            species
+                .into_iter()
+                .map(|(molecule, amount)| {
+                    let weight = molecule.relative_formula_mass();
+                    (molecule, amount / weight)
+                })
+                .collect(),
+        )
+    }
+
+    /// The same composition with duplicates merged and percentages summing to 100
+    pub fn normalised(&self) -> Option<Composition> {
+        Composition::new(self.0.clone())
+    }
+
+    /// The sum of all the percentages, 100 for a normalised composition
+    pub fn total(&self) -> f64 {
+        self.0.iter().map(|(_, ratio)| ratio).sum()
+    }
+
+    /// The percentage of molecules that are `molecule`
+    pub fn fraction_of(&self, molecule: &Molecule) -> f64 {
+        let total = self.total();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.0
+            .iter()
+            .filter(|(other, _)| other == molecule)
+            .map(|(_, ratio)| ratio)
+            .sum::<f64>()
+            / total
+            * 100.0
+    }
+
+    /// The percentage of the mass carried by each molecule
+    pub fn mass_fractions(&self) -> Vec<(Molecule, f64)> {
+        let total = self.mean_molecular_weight() * self.total();
+        if total <= 0.0 {
+            return vec![];
+        }
+        self.0
+            .iter()
+            .map(|(molecule, ratio)| {
+                (
+                    molecule.clone(),
+                    molecule.relative_formula_mass() * ratio / total * 100.0,
+                )
+            })
+            .collect()
+    }
+
+    /// The composition of `self_mass` of this gas mixed with `other_mass` of another
+    pub fn mix(
+        &self,
+        self_mass: Mass,
+        other: &Composition,
+        other_mass: Mass,
+    ) -> Option<Composition> {
+        let total = self_mass + other_mass;
+        let weighted = |composition: &Composition, mass: Mass| {
+            composition
+                .mass_fractions()
+                .into_iter()
+                .map(move |(molecule, fraction)| (molecule, fraction * (mass / total).value))
+        };
+        Composition::from_mass_fractions(
+            weighted(self, self_mass)
+                .chain(weighted(other, other_mass))
+                .collect(),
+        )
+    }
+
+    /// Add `molecule` so that it makes up `percent` of the molecules, scaling everything else
+    /// down to make room. A molecule already present is topped up to `percent`.
+    pub fn add(&self, molecule: Molecule, percent: f64) -> Option<Composition> {
+        if !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+        let rest = self.remove(&molecule);
+        match rest {
+            Some(rest) => Composition::new(
+                rest.0
+                    .into_iter()
+                    .map(|(other, ratio)| (other, ratio * (100.0 - percent) / 100.0))
+                    .chain([(molecule, percent)])
+                    .collect(),
+            ),
+            None => Composition::new(vec![(molecule, percent)]),
+        }
+    }
+
+    /// Take `molecule` out entirely, scaling everything else back up.
+    /// Returns `None` if nothing would be left.
+    pub fn remove(&self, molecule: &Molecule) -> Option<Composition> {
+        Composition::new(
+            self.0
+                .iter()
+                .filter(|(other, _)| other != molecule)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// The mean molecular weight, μ, in daltons: the average mass of a molecule in the gas
+    pub fn mean_molecular_weight(&self) -> f64 {
+        let total = self.total();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.0.iter().fold(0.0, |acc, (material, ratio)| {
+            acc + (material.relative_formula_mass() * ratio)
+        }) / total
+    }
+
+    pub fn mean_molecular_mass(&self) -> Mass {
+        Mass::new::<dalton>(self.mean_molecular_weight())
+    }
+
+    pub fn mean_molar_mass(&self) -> MolarMass {
+        MolarMass::new::<gram_per_mole>(self.mean_molecular_weight())
+    }
+
+    /// The heat capacity at constant volume of a mole of the gas, averaged over its molecules
+    pub fn molar_heat_capacity(&self) -> MolarHeatCapacity {
+        let total = self.total();
+        if total <= 0.0 {
+            return MolarHeatCapacity::new::<joule_per_kelvin_mole>(0.0);
+        }
+        MolarHeatCapacity::new::<joule_per_kelvin_mole>(
+            self.0.iter().fold(0.0, |acc, (material, ratio)| {
+                acc + material.degrees_of_freedom() / 2.0 * GAS_CONSTANT * ratio
+            }) / total,
+        )
+    }
+
+    /// The fraction of the mass that is locked up in dust grains
+    pub fn dust_mass_fraction(&self) -> f64 {
+        self.mass_fractions()
+            .iter()
+            .filter(|(molecule, _)| molecule.is_dust())
+            .map(|(_, fraction)| fraction / 100.0)
+            .sum()
+    }
+
+    /// Every atom in the gas, split by isotope, with the number of each per hundred molecules
+    fn atoms(&self) -> Vec<(Element, f64)> {
+        let mut atoms: Vec<(Element, f64)> = vec![];
+        for (molecule, ratio) in &self.0 {
+            for (element, count) in molecule.components() {
+                let number = *count as f64 * ratio;
+                match atoms.iter_mut().find(|(other, _)| other == element) {
+                    Some((_, total)) => *total += number,
+                    None => atoms.push((element.clone(), number)),
+                }
+            }
+        }
+        atoms
+    }
+
+    /// The elements that make up the gas, most common first
+    pub fn elements(&self) -> Vec<ElementalAbundance> {
+        let atoms = self.atoms();
+        let total_number: f64 = atoms.iter().map(|(_, number)| number).sum();
+        let total_mass: f64 = atoms
+            .iter()
+            .map(|(isotope, number)| isotope.data().atomic_mass_number() * number)
+            .sum();
+
+        let mut elements: Vec<ElementalAbundance> = vec![];
+        for (isotope, number) in atoms {
+            let mass = isotope.data().atomic_mass_number() * number;
+            match elements
+                .iter_mut()
+                .find(|element| element.symbol == isotope.symbol())
+            {
+                Some(element) => {
+                    element.number_fraction += number / total_number;
+                    element.mass_fraction += mass / total_mass;
+                }
+                None => elements.push(ElementalAbundance {
+                    symbol: isotope.symbol(),
+                    number_fraction: number / total_number,
+                    mass_fraction: mass / total_mass,
+                }),
+            }
+        }
+        elements.sort_by(|a, b| b.number_fraction.total_cmp(&a.number_fraction));
+        elements
+    }
+
+    /// The share of `symbol`'s atoms taken by each of its isotopes, most common first
+    pub fn isotopes(&self, symbol: &str) -> Vec<IsotopicAbundance> {
+        let atoms: Vec<(Element, f64)> = self
+            .atoms()
+            .into_iter()
+            .filter(|(isotope, _)| isotope.symbol() == symbol)
+            .collect();
+        let total_number: f64 = atoms.iter().map(|(_, number)| number).sum();
+        let total_mass: f64 = atoms
+            .iter()
+            .map(|(isotope, number)| isotope.data().atomic_mass_number() * number)
+            .sum();
+
+        let mut isotopes: Vec<IsotopicAbundance> = atoms
+            .into_iter()
+            .map(|(isotope, number)| IsotopicAbundance {
+                number_fraction: number / total_number,
+                mass_fraction: isotope.data().atomic_mass_number() * number / total_mass,
+                isotope,
+            })
+            .collect();
+        isotopes.sort_by(|a, b| b.number_fraction.total_cmp(&a.number_fraction));
+        isotopes
+    }
+
+    /// The number of atoms of `isotope` for each atom of `reference`, such as D/H or ¹³C/¹²C.
+    /// Returns `None` if there is no `reference` in the gas.
+    pub fn isotope_ratio(&self, isotope: &Element, reference: &Element) -> Option<f64> {
+        let atoms = self.atoms();
+        let count = |wanted: &Element| {
+            atoms
+                .iter()
+                .find(|(other, _)| other == wanted)
+                .map_or(0.0, |(_, number)| *number)
+        };
+        let reference = count(reference);
+        if reference > 0.0 {
+            Some(count(isotope) / reference)
+        } else {
+            None
+        }
+    }
+
+    /// The mass fractions of hydrogen, helium and metals, counting every isotope of each
+    pub fn metallicity(&self) -> Metallicity {
+        let elements = self.elements();
+        let fraction = |symbol: &str| {
+            elements
+                .iter()
+                .find(|element| element.symbol == symbol)
+                .map_or(0.0, |element| element.mass_fraction)
+        };
+        Metallicity {
+            x: fraction("H"),
+            y: fraction("He"),
+            z: elements
+                .iter()
+                .filter(|element| element.symbol != "H" && element.symbol != "He")
+                .map(|element| element.mass_fraction)
+                .sum(),
+        }
+    }
+
+    /// The logarithmic abundance of an element relative to hydrogen, compared with the Sun:
+    /// [X/H] = log₁₀(N_X / N_H) - log₁₀(N_X / N_H)☉. Zero is solar, -1 a tenth of solar.
+    /// Returns `None` without hydrogen, without the element, or without a solar reference for it.
+    pub fn abundance_index(&self, symbol: &str) -> Option<f64> {
+        let (_, solar) = SOLAR_ABUNDANCES
+            .iter()
+            .find(|(reference, _)| *reference == symbol)?;
+        let elements = self.elements();
+        let number = |symbol: &str| {
+            elements
+                .iter()
+                .find(|element| element.symbol == symbol)
+                .map(|element| element.number_fraction)
+        };
+        let (element, hydrogen) = (number(symbol)?, number("H")?);
+        Some((element / hydrogen).log10() + 12.0 - solar)
+    }
+
+    /// The overall metallicity relative to the Sun, [M/H] = log₁₀(Z/X) - log₁₀(Z/X)☉. This stands
+    /// in for [Fe/H], as iron isn't among the elements that can be modelled.
+    /// Returns `None` for a gas with no hydrogen or no metals.
+    pub fn metallicity_index(&self) -> Option<f64> {
+        let metallicity = self.metallicity();
+        if metallicity.x <= 0.0 || metallicity.z <= 0.0 {
+            return None;
+        }
+        Some(
+            (metallicity.z / metallicity.x).log10()
+                - (SOLAR_METALLICITY.z / SOLAR_METALLICITY.x).log10(),
+        )
+    }
+}
+
+impl Interpolatable for Composition {
+    type AO = Interpolationf64AsyncOptions;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    /// Ratios are blended in `space`, but a molecule present on only one side has a ratio of zero
+    /// on the other, so it fades in or out linearly in the logarithmic space.
+    /// Blending outside linear space does not keep the ratios' total, so they are scaled back to
+    /// the total a linear blend would give: 100 between normalised compositions.
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        // molecules missing from either side fade in or out from zero
+        let ratios = |composition: &Composition| {
+            let mut ratios: HashMap<Molecule, f64> = HashMap::new();
+            composition.0.iter().for_each(|(molecule, ratio)| {
+                *ratios.entry(molecule.clone()).or_insert(0.0) += ratio;
+            });
+            ratios
+        };
+
+        let blended = ratios(self).interpolate_in_space(
+            &ratios(other),
+            transition,
+            ease,
+            space,
+            asynchronous_options,
+        );
+        let total: f64 = blended.values().sum();
+        let scale = if space == InterpolationSpace::Linear || total <= 0.0 {
+            1.0
+        } else {
+            self.total()
+                .interpolate(&other.total(), transition, ease, asynchronous_options)
+                / total
+        };
+
+        // keep the order molecules first appear in, rather than the map's
+        let mut molecules: Vec<&Molecule> = vec![];
+        for (molecule, _) in self.0.iter().chain(other.0.iter()) {
+            if !molecules.contains(&molecule) {
+                molecules.push(molecule);
+            }
+        }
+        Composition(
+            molecules
+                .into_iter()
+                .map(|molecule| (molecule.clone(), blended[molecule] * scale))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,405 @@
+#[cfg(test)]
+mod tests {
+    use crate::chemistry::elements::carbon::CarbonIsotope;
+    use crate::chemistry::elements::elements::Element;
+    use crate::chemistry::elements::hydrogen::HydrogenIsotope;
+    use crate::chemistry::elements::oxygen::OxygenIsotope;
+    use crate::chemistry::elements::silicon::SiliconIsotope;
+    use crate::chemistry::molecules::molecules::Molecule;
+    use crate::gas::composition::{Composition, SOLAR_ABUNDANCES, SOLAR_METALLICITY};
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::Mass;
+    use uom::si::mass::kilogram;
+
+    fn ratio(composition: &Composition, molecule: &Molecule) -> f64 {
+        composition.fraction_of(molecule)
+    }
+
+    #[test]
+    fn composition_normalises_and_merges() {
+        let composition = Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 3.0),
+            (Molecule::atomic_helium(), 1.0),
+            (Molecule::molecular_hydrogen(), 4.0),
+            (Molecule::water(), 0.0),
+        ])
+        .unwrap();
+        assert_eq!(composition.0.len(), 2);
+        assert_approx_eq!(f64, composition.total(), 100.0, epsilon = 1e-12);
+        assert_approx_eq!(
+            f64,
+            ratio(&composition, &Molecule::molecular_hydrogen()),
+            87.5,
+            epsilon = 1e-12
+        );
+
+        assert!(Composition::new(vec![]).is_none());
+        assert!(Composition::new(vec![(Molecule::water(), 0.0)]).is_none());
+        assert!(Composition::new(vec![(Molecule::water(), -1.0)]).is_none());
+
+        // hand written compositions can be tidied up afterwards
+        let loose = Composition(vec![
+            (Molecule::atomic_hydrogen(), 1.0),
+            (Molecule::atomic_hydrogen(), 1.0),
+        ]);
+        assert_eq!(
+            loose.normalised().unwrap().0,
+            vec![(Molecule::atomic_hydrogen(), 100.0)]
+        );
+    }
+
+    #[test]
+    fn composition_number_and_mass_fractions() {
+        // one helium atom weighs about as much as two hydrogen molecules
+        let composition = Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 50.0),
+            (Molecule::atomic_helium(), 50.0),
+        ])
+        .unwrap();
+        let mass_fractions = composition.mass_fractions();
+        let helium = mass_fractions
+            .iter()
+            .find(|(molecule, _)| *molecule == Molecule::atomic_helium())
+            .unwrap()
+            .1;
+        assert_approx_eq!(f64, helium, 66.5, epsilon = 0.1);
+
+        let round_trip = Composition::from_mass_fractions(mass_fractions).unwrap();
+        assert_approx_eq!(
+            f64,
+            ratio(&round_trip, &Molecule::atomic_helium()),
+            50.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn composition_mean_molecular_weight() {
+        let composition = Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 90.0),
+            (Molecule::atomic_helium(), 10.0),
+        ])
+        .unwrap();
+        let expected = 0.9 * Molecule::molecular_hydrogen().relative_formula_mass()
+            + 0.1 * Molecule::atomic_helium().relative_formula_mass();
+        assert_approx_eq!(
+            f64,
+            composition.mean_molecular_weight(),
+            expected,
+            epsilon = 1e-12
+        );
+        // unnormalised percentages give the same answer
+        let doubled = Composition(
+            composition
+                .0
+                .iter()
+                .map(|(molecule, ratio)| (molecule.clone(), ratio * 2.0))
+                .collect(),
+        );
+        assert_approx_eq!(
+            f64,
+            doubled.mean_molecular_weight(),
+            expected,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn composition_with_nothing_in_it() {
+        for empty in [
+            Composition(vec![]),
+            Composition(vec![(Molecule::molecular_hydrogen(), 0.0)]),
+        ] {
+            assert_eq!(ratio(&empty, &Molecule::molecular_hydrogen()), 0.0);
+            assert!(empty.mass_fractions().is_empty());
+            assert_eq!(empty.mean_molecular_weight(), 0.0);
+            assert_eq!(empty.molar_heat_capacity().value, 0.0);
+            assert_eq!(empty.dust_mass_fraction(), 0.0);
+        }
+    }
+
+    #[test]
+    fn composition_mixing() {
+        let hydrogen = Composition::new(vec![(Molecule::molecular_hydrogen(), 1.0)]).unwrap();
+        let helium = Composition::new(vec![(Molecule::atomic_helium(), 1.0)]).unwrap();
+
+        let mixed = hydrogen
+            .mix(
+                Mass::new::<kilogram>(1.0),
+                &helium,
+                Mass::new::<kilogram>(1.0),
+            )
+            .unwrap();
+        let mass_fractions = mixed.mass_fractions();
+        assert!(mass_fractions
+            .iter()
+            .all(|(_, fraction)| (fraction - 50.0).abs() < 1e-9));
+        // an equal mass of hydrogen has about twice as many molecules
+        assert!(ratio(&mixed, &Molecule::molecular_hydrogen()) > 66.0);
+
+        let unchanged = hydrogen
+            .mix(
+                Mass::new::<kilogram>(1.0),
+                &hydrogen,
+                Mass::new::<kilogram>(5.0),
+            )
+            .unwrap();
+        assert_eq!(unchanged.0, hydrogen.0);
+    }
+
+    #[test]
+    fn composition_add_and_remove() {
+        let composition = Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 80.0),
+            (Molecule::atomic_helium(), 20.0),
+        ])
+        .unwrap();
+
+        let with_water = composition.add(Molecule::water(), 10.0).unwrap();
+        assert_approx_eq!(f64, with_water.total(), 100.0, epsilon = 1e-9);
+        assert_approx_eq!(
+            f64,
+            ratio(&with_water, &Molecule::water()),
+            10.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            ratio(&with_water, &Molecule::molecular_hydrogen()),
+            72.0,
+            epsilon = 1e-9
+        );
+
+        // topping up an existing species
+        let more_helium = composition.add(Molecule::atomic_helium(), 50.0).unwrap();
+        assert_approx_eq!(
+            f64,
+            ratio(&more_helium, &Molecule::molecular_hydrogen()),
+            50.0,
+            epsilon = 1e-9
+        );
+        assert!(composition.add(Molecule::water(), 101.0).is_none());
+
+        // H2O shares its first component with H2, and must not be mistaken for it
+        let without_water = with_water.remove(&Molecule::water()).unwrap();
+        assert_eq!(without_water.0.len(), 2);
+        assert_approx_eq!(
+            f64,
+            ratio(&without_water, &Molecule::molecular_hydrogen()),
+            80.0,
+            epsilon = 1e-9
+        );
+        assert!(Composition::new(vec![(Molecule::water(), 1.0)])
+            .unwrap()
+            .remove(&Molecule::water())
+            .is_none());
+    }
+
+    #[test]
+    fn composition_metallicity() {
+        let primordial = Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 92.0),
+            (Molecule::atomic_helium(), 16.0),
+        ])
+        .unwrap();
+        let metallicity = primordial.metallicity();
+        assert_eq!(metallicity.z, 0.0);
+        assert_approx_eq!(f64, metallicity.x + metallicity.y, 1.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, metallicity.y, 0.256, epsilon = 0.01);
+
+        let enriched = primordial
+            .add(Molecule::carbon_monoxide(), 0.1)
+            .unwrap()
+            .add(
+                Molecule::new(vec![
+                    (Element::Silicon(SiliconIsotope::Silicon), 1),
+                    (Element::Hydrogen(HydrogenIsotope::Deuterium), 4),
+                ]),
+                0.01,
+            )
+            .unwrap();
+        let metallicity = enriched.metallicity();
+        assert!(metallicity.z > 0.0 && metallicity.z < 0.05);
+        assert_approx_eq!(
+            f64,
+            metallicity.x + metallicity.y + metallicity.z,
+            1.0,
+            epsilon = 1e-12
+        );
+        assert!(enriched.dust_mass_fraction() > 0.0);
+        assert_eq!(primordial.dust_mass_fraction(), 0.0);
+    }
+
+    fn interstellar() -> Composition {
+        Composition::new(vec![
+            (Molecule::molecular_hydrogen(), 90.0),
+            (
+                Molecule::new(vec![
+                    (Element::Hydrogen(HydrogenIsotope::Hydrogen), 1),
+                    (Element::Hydrogen(HydrogenIsotope::Deuterium), 1),
+                ]),
+                0.01,
+            ),
+            (Molecule::atomic_helium(), 15.0),
+            (Molecule::carbon_monoxide(), 0.02),
+            (
+                Molecule::new(vec![
+                    (Element::Carbon(CarbonIsotope::Caron13), 1),
+                    (Element::Oxygen(OxygenIsotope::Oxygen), 1),
+                ]),
+                0.0002,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn composition_elemental_breakdown() {
+        let elements = interstellar().elements();
+        let mut symbols: Vec<&str> = elements.iter().map(|element| element.symbol).collect();
+        assert_eq!(symbols[..2], ["H", "He"]);
+        symbols.sort();
+        assert_eq!(symbols, vec!["C", "H", "He", "O"]);
+        assert_approx_eq!(
+            f64,
+            elements.iter().map(|e| e.number_fraction).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_approx_eq!(
+            f64,
+            elements.iter().map(|e| e.mass_fraction).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+
+        // two hydrogen atoms per molecule, deuterium included
+        let (hydrogen, helium) = (&elements[0], &elements[1]);
+        assert_approx_eq!(
+            f64,
+            hydrogen.number_fraction / helium.number_fraction,
+            (180.0 + 0.02) / 15.0,
+            epsilon = 1e-9
+        );
+        // carbon and oxygen come in pairs, whichever isotope
+        assert_approx_eq!(
+            f64,
+            elements[2].number_fraction,
+            elements[3].number_fraction,
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn composition_isotopic_breakdown() {
+        let composition = interstellar();
+        let hydrogen = composition.isotopes("H");
+        assert_eq!(hydrogen.len(), 2);
+        assert_eq!(
+            hydrogen[0].isotope,
+            Element::Hydrogen(HydrogenIsotope::Hydrogen)
+        );
+        assert!(hydrogen[1].mass_fraction > hydrogen[1].number_fraction);
+
+        let deuterium = composition
+            .isotope_ratio(
+                &Element::Hydrogen(HydrogenIsotope::Deuterium),
+                &Element::Hydrogen(HydrogenIsotope::Hydrogen),
+            )
+            .unwrap();
+        assert_approx_eq!(f64, deuterium, 0.01 / 180.01, epsilon = 1e-12);
+
+        let carbon = composition
+            .isotope_ratio(
+                &Element::Carbon(CarbonIsotope::Caron13),
+                &Element::Carbon(CarbonIsotope::Carbon),
+            )
+            .unwrap();
+        assert_approx_eq!(f64, carbon, 0.01, epsilon = 1e-12);
+
+        assert!(composition
+            .isotope_ratio(
+                &Element::Silicon(SiliconIsotope::Silicon29),
+                &Element::Silicon(SiliconIsotope::Silicon),
+            )
+            .is_none());
+        assert!(composition.isotopes("Si").is_empty());
+    }
+
+    #[test]
+    fn composition_solar_comparison() {
+        // build a gas with the Sun's own carbon and oxygen abundances
+        let solar = |symbol: &str| {
+            10.0_f64.powf(
+                SOLAR_ABUNDANCES
+                    .iter()
+                    .find(|(reference, _)| *reference == symbol)
+                    .unwrap()
+                    .1
+                    - 12.0,
+            )
+        };
+        let composition = Composition::new(vec![
+            (Molecule::atomic_hydrogen(), 1.0),
+            (Molecule::atomic_helium(), solar("He")),
+            (Molecule::carbon_monoxide(), solar("C")),
+            (
+                Molecule::new(vec![(Element::Oxygen(OxygenIsotope::Oxygen), 1)]),
+                solar("O") - solar("C"),
+            ),
+        ])
+        .unwrap();
+
+        assert_approx_eq!(
+            f64,
+            composition.abundance_index("C").unwrap(),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            composition.abundance_index("O").unwrap(),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert!(composition.abundance_index("Fe").is_none());
+        assert!(composition.abundance_index("Xx").is_none());
+
+        // carbon, oxygen and helium alone come close to the Sun's overall metallicity
+        let index = composition.metallicity_index().unwrap();
+        assert!(index < 0.0 && index > -0.5);
+        let metallicity = composition.metallicity();
+        assert_approx_eq!(f64, metallicity.y, SOLAR_METALLICITY.y, epsilon = 0.02);
+
+        // a tenth of the metals is one dex lower
+        let poor = Composition::new(vec![
+            (Molecule::atomic_hydrogen(), 1.0),
+            (Molecule::atomic_helium(), solar("He")),
+            (Molecule::carbon_monoxide(), solar("C") / 10.0),
+            (
+                Molecule::new(vec![(Element::Oxygen(OxygenIsotope::Oxygen), 1)]),
+                (solar("O") - solar("C")) / 10.0,
+            ),
+        ])
+        .unwrap();
+        assert_approx_eq!(
+            f64,
+            poor.abundance_index("C").unwrap(),
+            -1.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            poor.metallicity_index().unwrap(),
+            index - 1.0,
+            epsilon = 0.01
+        );
+
+        let primordial = Composition::new(vec![
+            (Molecule::atomic_hydrogen(), 1.0),
+            (Molecule::atomic_helium(), 0.08),
+        ])
+        .unwrap();
+        assert!(primordial.metallicity_index().is_none());
+    }
+}
@@ -0,0 +1,98 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::coordinates::position::Position;
+use crate::formulae::formulae::length;
+use crate::gas::gas::UniformGas;
+use crate::hash::seed::Seed;
+
+/// How a Jeans-unstable gas breaks up.
+#[derive(Debug, Clone, Copy)]
+pub struct FragmentationOptions {
+    /// The most pieces a gas splits into at once. Anything more massive splits into this many and
+    /// leaves the rest to the next level of the hierarchy.
+    pub branching: usize,
+    /// How many levels of splitting to allow before unstable fragments are kept as they are
+    pub max_depth: usize,
+    /// How far each piece's share of the mass strays from an even split, from 0 for identical
+    /// pieces up to, but not including, 1
+    pub mass_spread: f64,
+}
+
+/// A stable piece of a fragmented gas.
+#[derive(Debug, Clone)]
+pub struct Fragment {
+    pub gas: UniformGas,
+    /// Where the fragment's centre sits relative to the centre of the original gas
+    pub offset: Position,
+    /// How many times the gas was split to make this fragment
+    pub depth: usize,
+}
+
+/// Break `gas` into fragments of around a Jeans mass, splitting again any piece that is still
+/// unstable. A stable gas comes back whole.
+///
+/// Fragmentation is isothermal and quick compared to collapse, so every fragment keeps the
+/// parent's density, temperature and composition, and the fragments' masses, moles and volumes
+/// add up to the parent's. Each fragment sits at a random point inside its parent, drawn from
+/// `seed`, so the same seed always gives the same fragments. Fragments may overlap.
+pub fn fragment(gas: &UniformGas, seed: Seed, options: &FragmentationOptions) -> Vec<Fragment> {
+    let mut fragments = vec![];
+    split(gas, Position::origin(), 0, seed, options, &mut fragments);
+    fragments
+}
+
+fn split(
+    gas: &UniformGas,
+    offset: Position,
+    depth: usize,
+    seed: Seed,
+    options: &FragmentationOptions,
+    fragments: &mut Vec<Fragment>,
+) {
+    let jeans_masses = (gas.mass / gas.jeans_mass()).value;
+    if jeans_masses < 1.0 || depth >= options.max_depth || options.branching < 2 {
+        fragments.push(Fragment {
+            gas: gas.clone(),
+            offset,
+            depth,
+        });
+        return;
+    }
+
+    let pieces = (jeans_masses.ceil() as usize).clamp(2, options.branching);
+    let mut rng = seed.derive("masses").rng();
+    let spread = options.mass_spread.clamp(0.0, 0.99);
+    let shares: Vec<f64> = (0..pieces)
+        .map(|_| 1.0 + spread * rng.gen_range(-1.0..=1.0))
+        .collect();
+    let total: f64 = shares.iter().sum();
+
+    let radius = length::sphere_radius_from_volume(gas.volume);
+    for (index, share) in shares.iter().enumerate() {
+        let piece = gas.portion(share / total);
+
+        // uniform within the part of the parent the whole piece fits inside
+        let mut rng = seed.derive("positions").derive(index).rng();
+        let room = radius - length::sphere_radius_from_volume(piece.volume);
+        let distance = room * rng.gen_range(0.0..1.0_f64).cbrt();
+        let cos_theta: f64 = rng.gen_range(-1.0..1.0);
+        let azimuth: f64 = rng.gen_range(0.0..2.0 * PI);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let direction = Position::new(
+            distance * sin_theta * azimuth.cos(),
+            distance * sin_theta * azimuth.sin(),
+            distance * cos_theta,
+        );
+
+        split(
+            &piece,
+            offset + direction,
+            depth + 1,
+            seed.derive("fragments").derive(index),
+            options,
+            fragments,
+        );
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use crate::chemistry::molecules::molecules::Molecule;
+    use crate::cloud::cloud::{CloudOptions, MolecularCloud};
+    use crate::coordinates::position::Position;
+    use crate::formulae::formulae::{length, volume};
+    use crate::gas::composition::Composition;
+    use crate::gas::fragmentation::{fragment, Fragment, FragmentationOptions};
+    use crate::gas::gas::UniformGas;
+    use crate::hash::seed::Seed;
+    use crate::Coordinates;
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, ThermodynamicTemperature};
+    use uom::si::length::{light_year, parsec};
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    const OPTIONS: FragmentationOptions = FragmentationOptions {
+        branching: 8,
+        max_depth: 10,
+        mass_spread: 0.3,
+    };
+
+    fn dense_core(radius: Length) -> UniformGas {
+        UniformGas::composite_from_vacuum_properties(
+            volume::sphere_volume_from_length(radius),
+            1e10,
+            ThermodynamicTemperature::new::<kelvin>(10.0),
+            Composition::new(vec![
+                (Molecule::molecular_hydrogen(), 90.0),
+                (Molecule::atomic_helium(), 9.0),
+                (Molecule::carbon_monoxide(), 1.0),
+            ])
+            .unwrap(),
+        )
+    }
+
+    fn assert_conserved(parent: &UniformGas, fragments: &[Fragment]) {
+        let total = |property: fn(&UniformGas) -> f64| -> f64 {
+            fragments
+                .iter()
+                .map(|fragment| property(&fragment.gas))
+                .sum()
+        };
+        assert_approx_eq!(
+            f64,
+            total(|gas| gas.mass.value) / parent.mass.value,
+            1.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            total(|gas| gas.moles.value) / parent.moles.value,
+            1.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            total(|gas| gas.volume.value) / parent.volume.value,
+            1.0,
+            epsilon = 1e-9
+        );
+        for fragment in fragments {
+            assert_eq!(fragment.gas.materials.0, parent.materials.0);
+            assert_eq!(fragment.gas.density, parent.density);
+            assert_eq!(fragment.gas.temperature, parent.temperature);
+        }
+    }
+
+    #[test]
+    fn fragmentation_leaves_stable_gas_whole() {
+        let core = dense_core(Length::new::<parsec>(0.01));
+        assert!(core.mass < core.jeans_mass());
+
+        let fragments = fragment(&core, Seed(1), &OPTIONS);
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].depth, 0);
+        assert_eq!(fragments[0].offset, Position::origin());
+        assert_eq!(fragments[0].gas.mass, core.mass);
+    }
+
+    #[test]
+    fn fragmentation_splits_until_stable() {
+        let core = dense_core(Length::new::<parsec>(0.5));
+        let jeans_masses = (core.mass / core.jeans_mass()).value;
+        assert!(jeans_masses > OPTIONS.branching as f64);
+
+        let fragments = fragment(&core, Seed(1), &OPTIONS);
+        assert!(fragments.len() as f64 >= jeans_masses);
+        assert_conserved(&core, &fragments);
+
+        let radius = length::sphere_radius_from_volume(core.volume);
+        for fragment in &fragments {
+            assert!(fragment.gas.mass < fragment.gas.jeans_mass());
+            // too massive to stop after one split
+            assert!(fragment.depth > 1);
+            let extent = fragment.offset.magnitude()
+                + length::sphere_radius_from_volume(fragment.gas.volume);
+            assert!(extent.value <= radius.value * (1.0 + 1e-9));
+        }
+    }
+
+    #[test]
+    fn fragmentation_is_deterministic() {
+        let core = dense_core(Length::new::<parsec>(0.2));
+        let offsets = |seed: Seed| -> Vec<Position> {
+            fragment(&core, seed, &OPTIONS)
+                .into_iter()
+                .map(|fragment| fragment.offset)
+                .collect()
+        };
+        assert_eq!(offsets(Seed(7)), offsets(Seed(7)));
+        assert_ne!(offsets(Seed(7)), offsets(Seed(8)));
+    }
+
+    #[test]
+    fn fragmentation_stops_at_max_depth() {
+        let core = dense_core(Length::new::<parsec>(0.5));
+        let options = FragmentationOptions {
+            branching: 4,
+            max_depth: 1,
+            mass_spread: 0.0,
+        };
+
+        let fragments = fragment(&core, Seed(3), &options);
+        assert_eq!(fragments.len(), 4);
+        assert_conserved(&core, &fragments);
+        for fragment in &fragments {
+            assert_eq!(fragment.depth, 1);
+            assert_approx_eq!(
+                f64,
+                fragment.gas.mass.value,
+                core.mass.value / 4.0,
+                epsilon = core.mass.value * 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn fragmentation_of_molecular_cloud() {
+        let contents = vec![
+            (Molecule::molecular_hydrogen(), 90.0),
+            (Molecule::atomic_helium(), 10.0),
+        ];
+        let cloud = MolecularCloud::new(
+            Coordinates::new(4, 2, 0),
+            Length::new::<light_year>(3.0),
+            UniformGas::generate_composite_massdensity(&Composition(contents.clone()), 1e10),
+            contents,
+            CloudOptions {
+                use_randomness: false,
+                core_formation_chance: 0.0,
+                core_formation_dropoff: 0.0,
+                turbulence: None,
+            },
+        );
+        let temperature = ThermodynamicTemperature::new::<kelvin>(10.0);
+
+        let fragments = cloud.fragment(temperature, &OPTIONS).unwrap();
+        assert!(fragments.len() > 1);
+        let mass: f64 = fragments
+            .iter()
+            .map(|fragment| fragment.gas.mass.value)
+            .sum();
+        assert_approx_eq!(f64, mass / cloud.mass.value, 1.0, epsilon = 1e-9);
+
+        let again = cloud.fragment(temperature, &OPTIONS).unwrap();
+        assert_eq!(
+            fragments
+                .iter()
+                .map(|fragment| fragment.offset)
+                .collect::<Vec<_>>(),
+            again
+                .iter()
+                .map(|fragment| fragment.offset)
+                .collect::<Vec<_>>()
+        );
+    }
+}
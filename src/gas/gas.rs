@@ -1,12 +1,15 @@
 use crate::chemistry::molecules::molecules::Molecule;
 use crate::formulae::constants::{AVOGADRO_CONSTANT, GAS_CONSTANT};
 use crate::formulae::formulae::{density, energy, force, length, mass, time, wavelength};
+use crate::gas::composition::Composition;
 use crate::solar_mass;
-use crate::transition::transition::{EasingFunction, Interpolatable, Interpolationf64AsyncOptions};
-use crate::units::units::mass::dalton;
+use crate::transition::transition::{
+    remap_transition, EasingFunction, Interpolatable, InterpolationSpace,
+    Interpolationf64AsyncOptions,
+};
 use crate::units::units::time::{million_year, thousand_year};
 use crate::wavelength::wavelength::Wavelength;
-use std::collections::HashMap;
+use photon_derive::Interpolatable;
 use uom::fmt::DisplayStyle::Abbreviation;
 use uom::si::amount_of_substance::mole;
 use uom::si::energy::joule;
@@ -15,7 +18,6 @@ use uom::si::f64::{
     ThermodynamicTemperature, Time, Volume,
 };
 use uom::si::frequency::hertz;
-use uom::si::mass::kilogram;
 use uom::si::mass_density::kilogram_per_cubic_meter;
 use uom::si::pressure::pascal;
 use uom::si::thermodynamic_temperature::kelvin;
@@ -30,72 +32,26 @@ enum CosmicState {
 
 pub trait Gas {}
 
-#[derive(Debug, Clone)]
-pub struct Composition(pub Vec<(Molecule, f64)>);
-
-impl Interpolatable for Composition {
-    type AO = ();
-    fn interpolate(
-        &self,
-        other: &Self,
-        transition: f64,
-        ease: Option<EasingFunction>,
-        asynchronous_options: Option<Self::AO>,
-    ) -> Self {
-        // [a: 50.0, b: 30.0], [a: 20.0, b: 20.0, c: 60.0] - target has values that dont exist in origin
-        // [a: 20.0, b: 20.0, c: 60.0], [a: 50.0, b: 30.0] - origin has values that dont exist in target
-        // [a: 50.0, b: 50.0], [a: 30.0, b: 70.0] - parity between arrays
-
-        let mut origin: HashMap<Molecule, f64> = HashMap::new();
-        let mut target: HashMap<Molecule, f64> = HashMap::new();
-
-        self.0.iter().for_each(|(molecule, ratio)| {
-            let m = origin.entry(molecule.clone()).or_insert(0.0);
-            *m += ratio;
-        });
-
-        other.0.iter().for_each(|(molecule, ratio)| {
-            let m = target.entry(molecule.clone()).or_insert(0.0);
-            *m += ratio;
-        });
-
-        // first, fill the origin with empty values for each non-matching target
-        other.0.iter().for_each(|(molecule, _)| {
-            origin.entry(molecule.clone()).or_insert(0.0);
-        });
-
-        // and then do the same for the target map
-        self.0.iter().for_each(|(molecule, _)| {
-            target.entry(molecule.clone()).or_insert(0.0);
-        });
-
-        let mut new_composition = Composition(vec![]);
-        origin.into_iter().for_each(|(molecule, ratio)| {
-            let target_value = target
-                .get(&molecule)
-                .unwrap_or_else(|| panic!("cannot unwrap"));
-            new_composition.0.push((
-                molecule,
-                ratio.interpolate(target_value, transition, ease, None),
-            ));
-        });
-
-        new_composition
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Interpolatable)]
 pub struct UniformGas {
+    #[interpolatable(offset)]
     pub volume: Volume,
+    #[interpolatable(offset)]
     pub pressure: Pressure,
+    #[interpolatable(offset)]
     pub moles: AmountOfSubstance,
+    #[interpolatable(offset)]
     pub temperature: ThermodynamicTemperature,
 
+    #[interpolatable(offset)]
     pub materials: Composition,
 
+    #[interpolatable(offset)]
     pub mass: Mass,
+    #[interpolatable(offset)]
     pub density: MassDensity,
 
+    #[interpolatable(skip)]
     state: CosmicState,
 }
 
@@ -234,14 +190,7 @@ impl UniformGas {
         let density: MassDensity =
             UniformGas::generate_composite_massdensity(&materials, particles_per_cubic_meter);
         let mass = mass::from_volume_and_density(volume, density);
-        let moles: AmountOfSubstance = materials.0.iter().fold(
-            AmountOfSubstance::new::<mole>(0.0),
-            |acc, (material, ratio)| {
-                acc + AmountOfSubstance::new::<mole>(
-                    (mass.value / material.molar_mass().value) * (ratio / 100.0),
-                )
-            },
-        );
+        let moles = UniformGas::moles_of(mass, &materials);
         UniformGas {
             volume,
             pressure: Pressure::new::<pascal>(
@@ -384,29 +333,16 @@ impl UniformGas {
     }
 
     fn average_particle_mass(&self) -> Mass {
-        self.materials
-            .0
-            .iter()
-            .fold(Mass::new::<kilogram>(0.0), |acc, (material, ratio)| {
-                material.molecular_weight() * (ratio / 100.0)
-            })
+        self.materials.mean_molecular_mass()
+    }
+
+    /// The mean molecular weight of the gas in daltons, weighted by the ratio of each material
+    pub fn mean_molecular_weight(&self) -> f64 {
+        self.materials.mean_molecular_weight()
     }
 
     pub fn jeans_radius(&self) -> Length {
-        let mean_molecular_weight = self.materials.0.iter().fold(0.0, |acc, (material, ratio)| {
-            println!(
-                "adding weight of {}. {:?}",
-                material,
-                material
-                    .molecular_weight()
-                    .into_format_args(dalton, Abbreviation)
-            );
-            acc + (material.relative_formula_mass() * ratio)
-        }) / 100.0;
-        // println!(
-        //     "mean weight {}",
-        //     mean_molecular_weight.into_format_args(dalton, Abbreviation)
-        // );
+        let mean_molecular_weight = self.mean_molecular_weight();
         length::jeans_radius(
             self.mass,
             length::sphere_radius_from_volume(self.volume),
@@ -435,77 +371,171 @@ impl UniformGas {
 
 impl Gas for UniformGas {}
 
-pub struct UniformGasAsynchronousOptions {
-    temperature_offset: Option<Interpolationf64AsyncOptions>,
+/// The pair of bulk properties a constrained interpolation blends directly. The third of volume,
+/// mass and density follows from ρ = m/V.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasInterpolationBasis {
+    VolumeAndMass,
+    VolumeAndDensity,
+    DensityAndMass,
 }
 
-impl Interpolatable for UniformGas {
-    type AO = UniformGasAsynchronousOptions;
+/// How to blend each independent property in a constrained interpolation. Spaces for properties
+/// outside the basis are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstrainedGasOptions {
+    pub basis: GasInterpolationBasis,
+    pub volume: InterpolationSpace,
+    pub mass: InterpolationSpace,
+    pub density: InterpolationSpace,
+    pub temperature: InterpolationSpace,
+}
 
-    fn interpolate(
+impl UniformGas {
+    /// Interpolate towards `target` so that every intermediate state is a valid gas.
+    ///
+    /// Only the basis properties, temperature and composition are blended. Moles follow from the
+    /// mass and composition, and pressure from the ideal gas law, so offsets given for
+    /// pressure or moles have no effect, nor does an offset for whichever of volume, mass and
+    /// density is outside the basis.
+    pub fn interpolate_constrained(
         &self,
-        target: &Self,
+        target: &UniformGas,
         transition: f64,
         ease: Option<EasingFunction>,
-        asynchronous_options: Option<Self::AO>,
-    ) -> Self {
-        let temperature_interpolation_offset: Option<Interpolationf64AsyncOptions> = {
-            match asynchronous_options {
-                Some(opts) => opts.temperature_offset,
-                None => None,
+        options: &ConstrainedGasOptions,
+        asynchronous_options: Option<UniformGasAsynchronousOptions>,
+    ) -> UniformGas {
+        let offsets = asynchronous_options.unwrap_or_default();
+        let progress = |offset: Option<Interpolationf64AsyncOptions>| {
+            let adjusted = offset.map_or(transition, |offset| {
+                remap_transition(transition, offset.0, 1.0)
+            });
+            ease.map_or(adjusted, |ease| ease(adjusted))
+        };
+
+        let volume = || {
+            options.volume.lerp_quantity(
+                self.volume,
+                target.volume,
+                progress(offsets.volume_offset),
+            )
+        };
+        let mass = || {
+            options
+                .mass
+                .lerp_quantity(self.mass, target.mass, progress(offsets.mass_offset))
+        };
+        let density = || {
+            options.density.lerp_quantity(
+                self.density,
+                target.density,
+                progress(offsets.density_offset),
+            )
+        };
+        let (volume, mass, density) = match options.basis {
+            GasInterpolationBasis::VolumeAndMass => {
+                let (volume, mass) = (volume(), mass());
+                (volume, mass, mass / volume)
+            }
+            GasInterpolationBasis::VolumeAndDensity => {
+                let (volume, density) = (volume(), density());
+                (volume, density * volume, density)
+            }
+            GasInterpolationBasis::DensityAndMass => {
+                let (density, mass) = (density(), mass());
+                (mass / density, mass, density)
             }
         };
-        let temperature =
-            ThermodynamicTemperature::new::<kelvin>(self.temperature.value.interpolate(
-                &target.temperature.value,
-                transition,
-                ease,
-                temperature_interpolation_offset,
-            ));
-        let volume = Volume::new::<cubic_meter>(self.volume.value.interpolate(
-            &target.volume.value,
-            transition,
-            ease,
-            None,
-        ));
-        let pressure = Pressure::new::<pascal>(self.pressure.value.interpolate(
-            &target.pressure.value,
-            transition,
-            ease,
-            None,
-        ));
-        let moles = AmountOfSubstance::new::<mole>(self.moles.value.interpolate(
-            &target.moles.value,
-            transition,
-            ease,
-            None,
-        ));
-        let mass = Mass::new::<kilogram>(self.mass.value.interpolate(
-            &target.mass.value,
-            transition,
-            ease,
-            None,
-        ));
-        let density = MassDensity::new::<kilogram_per_cubic_meter>(self.density.value.interpolate(
-            &target.density.value,
+
+        let temperature = options.temperature.lerp_quantity(
+            self.temperature,
+            target.temperature,
+            progress(offsets.temperature_offset),
+        );
+        let materials = self.materials.interpolate(
+            &target.materials,
             transition,
             ease,
-            None,
-        ));
+            offsets.materials_offset,
+        );
+        let moles = UniformGas::moles_of(mass, &materials);
 
+        UniformGas {
+            volume,
+            pressure: Pressure::new::<pascal>(
+                (moles.value * GAS_CONSTANT * temperature.value) / volume.value,
+            ),
+            moles,
+            temperature,
+            materials,
+            mass,
+            density,
+            state: self.state.clone(),
+        }
+    }
+
+    /// The thermal energy held by the gas's molecules, U = n Cv T
+    pub fn internal_energy(&self) -> Energy {
+        self.moles * self.materials.molar_heat_capacity() * self.temperature
+    }
+
+    /// Merge `other` into this gas, as when two clouds collide or a core sweeps up the gas around
+    /// it.
+    ///
+    /// Mass, moles and internal energy are conserved. The composition is mixed by mass, the
+    /// temperature is whatever shares the combined internal energy across the combined heat
+    /// capacity, and the pressure follows from the ideal gas law. The merged gas fills `volume`,
+    /// or the two volumes together if it is `None`.
+    ///
+    /// Returns `None` if neither gas has any mass or `volume` is not positive.
+    pub fn merge(&self, other: &UniformGas, volume: Option<Volume>) -> Option<UniformGas> {
+        let volume = volume.unwrap_or(self.volume + other.volume);
+        if volume.value <= 0.0 {
+            return None;
+        }
         let materials = self
             .materials
-            .interpolate(&target.materials, transition, ease, None);
+            .mix(self.mass, &other.materials, other.mass)?;
+        let mass = self.mass + other.mass;
+        let moles = self.moles + other.moles;
+        let temperature = ThermodynamicTemperature::new::<kelvin>(
+            ((self.internal_energy() + other.internal_energy())
+                / (moles * materials.molar_heat_capacity()))
+            .value,
+        );
 
-        UniformGas {
+        Some(UniformGas {
             volume,
-            pressure,
+            pressure: Pressure::new::<pascal>(
+                (moles.value * GAS_CONSTANT * temperature.value) / volume.value,
+            ),
             moles,
             temperature,
             materials,
             mass,
-            density,
+            density: mass / volume,
+            state: self.state.clone(),
+        })
+    }
+
+    /// A share of this gas, `fraction` of its mass, at the same density, temperature and
+    /// composition
+    pub fn portion(&self, fraction: f64) -> UniformGas {
+        UniformGas {
+            volume: self.volume * fraction,
+            pressure: self.pressure,
+            moles: self.moles * fraction,
+            temperature: self.temperature,
+            materials: self.materials.clone(),
+            mass: self.mass * fraction,
+            density: self.density,
             state: self.state.clone(),
         }
     }
+
+    /// The amount of substance in `mass` of gas made of `materials`
+    fn moles_of(mass: Mass, materials: &Composition) -> AmountOfSubstance {
+        mass / materials.mean_molar_mass()
+    }
 }
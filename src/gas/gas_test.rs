@@ -6,6 +6,7 @@ mod tests {
     use crate::formulae::formulae::volume;
     use crate::gas::gas::UniformGas;
     use crate::{solar_mass, Composition};
+    use float_cmp::assert_approx_eq;
     use uom::fmt::DisplayStyle::Abbreviation;
     use uom::si::amount_of_substance::mole;
     use uom::si::f64::{
@@ -305,4 +306,101 @@ mod tests {
             MassDensity::new::<kilogram_per_cubic_meter>(3.346964268002621e-27)
         );
     }
+
+    #[test]
+    fn function_test_merge_conserves_mass_moles_and_energy() {
+        let volume = Volume::new::<cubic_meter>(1.0);
+        let moles = AmountOfSubstance::new::<mole>(1.0);
+        let hydrogen = UniformGas::from_volume_moles_temperature(
+            volume,
+            moles,
+            ThermodynamicTemperature::new::<kelvin>(10.0),
+            Molecule::molecular_hydrogen(),
+        );
+        let helium = UniformGas::from_volume_moles_temperature(
+            volume * 3.0,
+            moles,
+            ThermodynamicTemperature::new::<kelvin>(100.0),
+            Molecule::atomic_helium(),
+        );
+
+        let merged = hydrogen.merge(&helium, None).unwrap();
+        assert_eq!(merged.mass, hydrogen.mass + helium.mass);
+        assert_eq!(merged.moles, hydrogen.moles + helium.moles);
+        assert_eq!(merged.volume, Volume::new::<cubic_meter>(4.0));
+        assert_approx_eq!(
+            f64,
+            merged.internal_energy().value,
+            (hydrogen.internal_energy() + helium.internal_energy()).value,
+            epsilon = 1e-9
+        );
+        // hydrogen has more heat capacity per mole, so pulls the temperature its way
+        assert_approx_eq!(
+            f64,
+            merged.temperature.value,
+            (5.0 * 10.0 + 3.0 * 100.0) / 8.0,
+            epsilon = 1e-9
+        );
+        // equal moles of each
+        assert_approx_eq!(
+            f64,
+            merged.materials.fraction_of(&Molecule::atomic_helium()),
+            50.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            merged.pressure.value,
+            2.0 * 8.31446261815324 * merged.temperature.value / 4.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            merged.density.value,
+            merged.mass.value / 4.0,
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn function_test_merge_into_volume() {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(20.0);
+        let core = UniformGas::from_volume_moles_temperature(
+            Volume::new::<cubic_meter>(1.0),
+            AmountOfSubstance::new::<mole>(3.0),
+            temperature,
+            Molecule::molecular_hydrogen(),
+        );
+        let envelope = UniformGas::from_volume_moles_temperature(
+            Volume::new::<cubic_meter>(10.0),
+            AmountOfSubstance::new::<mole>(1.0),
+            temperature,
+            Molecule::molecular_hydrogen(),
+        );
+
+        // accreting gas at the same temperature leaves the temperature alone
+        let accreted = core
+            .merge(&envelope, Some(Volume::new::<cubic_meter>(2.0)))
+            .unwrap();
+        assert_approx_eq!(f64, accreted.temperature.value, 20.0, epsilon = 1e-12);
+        assert_eq!(accreted.volume, Volume::new::<cubic_meter>(2.0));
+        assert_approx_eq!(
+            f64,
+            accreted
+                .materials
+                .fraction_of(&Molecule::molecular_hydrogen()),
+            100.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            accreted.pressure.value,
+            core.pressure.value * 4.0 / 3.0 / 2.0,
+            epsilon = 1e-9
+        );
+
+        assert!(core
+            .merge(&envelope, Some(Volume::new::<cubic_meter>(0.0)))
+            .is_none());
+    }
 }
@@ -1,2 +1,6 @@
+pub mod composition;
+mod composition_test;
+pub mod fragmentation;
+mod fragmentation_test;
 pub mod gas;
 mod gas_test;
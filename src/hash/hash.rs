@@ -1,6 +1,8 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+use serde::{Deserialize, Serialize};
+
 fn transform_u32_to_array_of_u8(x: u32) -> [u8; 4] {
     let b1: u8 = ((x >> 24) & 0xff) as u8;
     let b2: u8 = ((x >> 16) & 0xff) as u8;
@@ -14,3 +16,57 @@ pub fn hash_int(num: u32) -> u64 {
     num.hash(&mut s);
     s.finish()
 }
+
+/// The increment of the SplitMix64 generator, the golden ratio scaled to 64 bits.
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A fixed starting state for coordinate hashes, so they differ from a plain SplitMix64 stream.
+const COORDINATE_SEED: u64 = 0x5048_4f54_4f4e_0001;
+
+/// The SplitMix64 finaliser (Steele, Lea & Flood 2014). A bijection on u64 where flipping any
+/// input bit flips each output bit with probability close to one half.
+pub fn split_mix_64(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The algorithm used to turn a set of coordinates into a seed.
+///
+/// Anything saved alongside a seed should also save the version that produced it, so that it
+/// can be regenerated identically after the default changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashVersion {
+    /// The original hash, built on the standard library's `DefaultHasher`. Its output is not
+    /// guaranteed to be stable between Rust releases and permuted coordinates can collide.
+    /// Only kept so that seeds saved before version 1 can be read back.
+    Legacy,
+    /// Each axis is folded into the state in turn with the SplitMix64 finaliser. Fully specified
+    /// here, so it will never change.
+    SplitMix64,
+}
+
+/// The version used for all newly generated coordinates.
+pub const CURRENT_HASH_VERSION: HashVersion = HashVersion::SplitMix64;
+
+fn legacy_hash<T: Hash>(t: T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+/// Hash a set of integer coordinates into a 64 bit seed.
+pub fn hash_coordinates(x: i64, y: i64, z: i64, version: HashVersion) -> u64 {
+    match version {
+        HashVersion::Legacy => {
+            let sum = legacy_hash(x + 1) as i128
+                + legacy_hash(y + 2) as i128
+                + legacy_hash(z + 3) as i128;
+            legacy_hash(sum)
+        }
+        HashVersion::SplitMix64 => [x, y, z].iter().fold(COORDINATE_SEED, |state, axis| {
+            split_mix_64(state.wrapping_add(GOLDEN_GAMMA) ^ *axis as u64)
+        }),
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::hash::hash::hash_int;
+    use crate::hash::hash::{hash_coordinates, hash_int, split_mix_64, HashVersion};
 
     #[test]
     fn hash_from_int() {
@@ -18,4 +18,27 @@ mod tests {
         assert_eq!(h5, 11141868147914754390);
         assert_eq!(h6, 692891098868956402);
     }
+
+    #[test]
+    fn split_mix_64_reference_value() {
+        // the first output of the reference SplitMix64 generator seeded with zero
+        assert_eq!(split_mix_64(0x9e37_79b9_7f4a_7c15), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn coordinate_hash_avalanche() {
+        // flipping a single input bit should flip about half of the output bits
+        let mut flipped = 0;
+        let mut trials = 0;
+        for x in 0..64_i64 {
+            let base = hash_coordinates(x, 7, -3, HashVersion::SplitMix64);
+            for bit in 0..64 {
+                let changed = hash_coordinates(x ^ (1 << bit), 7, -3, HashVersion::SplitMix64);
+                flipped += (base ^ changed).count_ones();
+                trials += 64;
+            }
+        }
+        let ratio = flipped as f64 / trials as f64;
+        assert!((ratio - 0.5).abs() < 0.01);
+    }
 }
@@ -1,2 +1,4 @@
 pub(crate) mod hash;
 mod hash_test;
+pub(crate) mod seed;
+mod seed_test;
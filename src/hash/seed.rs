@@ -0,0 +1,74 @@
+use std::hash::Hasher;
+
+use rand_chacha::ChaCha20Rng;
+use rand_seeder::rand_core::SeedableRng;
+use rand_seeder::SipHasher;
+use serde::{Deserialize, Serialize};
+
+/// The second SipHash key used when deriving child seeds. The first key is the parent seed.
+const DERIVATION_KEY: u64 = 0x7365_6564_6465_7269;
+
+/// A label that picks out one child stream of a seed.
+///
+/// Text and integers write distinct tags before their bytes, so the text "1" and the number 1
+/// derive different seeds. Every integer type is written as a `u64`, so an index derives the
+/// same seed whatever type it is held in, and a negative `i64` derives the same seed as the
+/// `u64` with the same bits.
+pub trait SeedLabel {
+    fn write_label(&self, hasher: &mut SipHasher);
+}
+
+impl SeedLabel for &str {
+    fn write_label(&self, hasher: &mut SipHasher) {
+        hasher.write(&[0]);
+        hasher.write(&(self.len() as u64).to_le_bytes());
+        hasher.write(self.as_bytes());
+    }
+}
+
+impl SeedLabel for u64 {
+    fn write_label(&self, hasher: &mut SipHasher) {
+        hasher.write(&[1]);
+        hasher.write(&self.to_le_bytes());
+    }
+}
+
+impl SeedLabel for i64 {
+    fn write_label(&self, hasher: &mut SipHasher) {
+        (*self as u64).write_label(hasher);
+    }
+}
+
+impl SeedLabel for usize {
+    fn write_label(&self, hasher: &mut SipHasher) {
+        (*self as u64).write_label(hasher);
+    }
+}
+
+impl SeedLabel for u32 {
+    fn write_label(&self, hasher: &mut SipHasher) {
+        (*self as u64).write_label(hasher);
+    }
+}
+
+/// The root of a tree of independent random streams.
+///
+/// Every subsystem derives its own child seed by name, and every numbered entity within it by
+/// index, e.g. `seed.derive("cores").derive(3)`. Drawing more numbers from one stream never
+/// changes another, and a derived seed only depends on its parent and its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Seed(pub u64);
+
+impl Seed {
+    /// The child seed named by `label`, hashed from this seed with SipHash-2-4.
+    pub fn derive<L: SeedLabel>(&self, label: L) -> Seed {
+        let mut hasher = SipHasher::from_keys(self.0, DERIVATION_KEY);
+        label.write_label(&mut hasher);
+        Seed(hasher.finish())
+    }
+
+    /// A random stream seeded by this seed.
+    pub fn rng(&self) -> ChaCha20Rng {
+        ChaCha20Rng::seed_from_u64(self.0)
+    }
+}
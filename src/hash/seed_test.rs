@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::coordinates::coordinates::{Cartesian, Coordinates};
+    use crate::hash::seed::Seed;
+    use rand::Rng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn seed_derivation_is_stable() {
+        // derived seeds are saved with universes, so these must never change
+        let seed = Seed(42);
+        assert_eq!(seed.derive("cores"), Seed(42).derive("cores"));
+        assert_eq!(seed.derive("cores").derive(3_usize).0, 11342508465725609432);
+    }
+
+    #[test]
+    fn seed_derivation_gives_independent_streams() {
+        let seed = Seed(7);
+        assert_ne!(seed.derive("cores"), seed.derive("stars"));
+        assert_ne!(seed.derive("cores"), seed);
+        assert_ne!(seed.derive(1_u64), seed.derive("1"));
+        assert_ne!(seed.derive("ab").derive("c"), seed.derive("a").derive("bc"));
+        assert_ne!(seed.derive(1_i64), seed.derive(1_i64).derive(1_i64));
+
+        // integers only differ by value, not by type
+        assert_eq!(seed.derive(1_i64), seed.derive(1_u64));
+        assert_eq!(seed.derive(1_u32), seed.derive(1_usize));
+        assert_eq!(seed.derive(-1_i64), seed.derive(u64::MAX));
+        assert_ne!(seed.derive(1_i64), seed.derive(2_u64));
+
+        let children: HashSet<Seed> = (0..10_000_usize).map(|i| seed.derive(i)).collect();
+        assert_eq!(children.len(), 10_000);
+    }
+
+    #[test]
+    fn seed_streams_are_unaffected_by_siblings() {
+        let seed = Seed(99);
+        let mut cores = seed.derive("cores").rng();
+        let first: f64 = cores.gen();
+
+        // drawing from a sibling stream leaves the first stream untouched
+        let mut stars = seed.derive("stars").rng();
+        (0..100).for_each(|_| {
+            stars.gen::<f64>();
+        });
+        let mut cores_again = seed.derive("cores").rng();
+        assert_eq!(first, cores_again.gen::<f64>());
+    }
+
+    #[test]
+    fn seed_from_coordinates() {
+        struct Point(Coordinates);
+        impl Cartesian for Point {
+            fn coordinates(&self) -> &Coordinates {
+                &self.0
+            }
+        }
+
+        let point = Point(Coordinates::new(5, -6, 7));
+        assert_eq!(point.seed(), Seed(point.0.hash));
+        assert_eq!(
+            point.get_rng().gen::<u64>(),
+            point.seed().rng().gen::<u64>()
+        );
+    }
+}
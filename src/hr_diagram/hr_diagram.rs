@@ -0,0 +1,200 @@
+use serde::Serialize;
+use uom::si::f64::{Power, ThermodynamicTemperature, Time};
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::time::year;
+
+use crate::formulae::formulae::{
+    area, calculate_absolute_magnitude, calculate_bv_index, calculate_temperature,
+};
+use crate::protostar::pre_main_sequence::PreMainSequenceStar;
+use crate::protostar::protostar::Protostar;
+use crate::spectral::spectral::SpectralType;
+use crate::units::units::power::solar_luminosity;
+
+/// Anything that can be placed on a Hertzsprung-Russell diagram.
+pub trait Luminous {
+    fn luminosity(&self) -> Power;
+    fn effective_temperature(&self) -> ThermodynamicTemperature;
+}
+
+/// A luminous body whose properties change over time, and so traces a track across the diagram.
+pub trait Evolving: Luminous {
+    fn age(&self) -> Time;
+}
+
+impl Luminous for PreMainSequenceStar {
+    fn luminosity(&self) -> Power {
+        self.luminosity
+    }
+
+    fn effective_temperature(&self) -> ThermodynamicTemperature {
+        self.effective_temperature
+    }
+}
+
+impl Evolving for PreMainSequenceStar {
+    fn age(&self) -> Time {
+        self.age
+    }
+}
+
+impl Luminous for Protostar {
+    fn luminosity(&self) -> Power {
+        self.accretion_luminosity
+    }
+
+    fn effective_temperature(&self) -> ThermodynamicTemperature {
+        calculate_temperature(
+            self.accretion_luminosity,
+            1.0,
+            area::sphere_surface_from_radius(self.radius),
+        )
+    }
+}
+
+impl Evolving for Protostar {
+    fn age(&self) -> Time {
+        self.age
+    }
+}
+
+/// A single point on the diagram.
+#[derive(Debug, Clone, Serialize)]
+pub struct HrPoint {
+    /// log₁₀ of the effective temperature in kelvin
+    pub log_temperature: f64,
+    /// log₁₀ of the luminosity in solar luminosities
+    pub log_luminosity: f64,
+    pub absolute_magnitude: f64,
+    pub bv_index: f64,
+    pub spectral_type: String,
+}
+
+impl HrPoint {
+    /// Place a body on the diagram. Bodies that emit no light cannot be plotted on the
+    /// logarithmic axes and produce `None`.
+    pub fn new<T: Luminous>(body: &T) -> Option<HrPoint> {
+        let luminosity = body.luminosity();
+        let temperature = body.effective_temperature();
+        if luminosity.value <= 0.0 || temperature.value <= 0.0 {
+            return None;
+        }
+
+        Some(HrPoint {
+            log_temperature: temperature.get::<kelvin>().log10(),
+            log_luminosity: luminosity.get::<solar_luminosity>().log10(),
+            absolute_magnitude: calculate_absolute_magnitude(luminosity),
+            bv_index: calculate_bv_index(temperature),
+            spectral_type: SpectralType::from_temperature_and_luminosity(temperature, luminosity)
+                .to_string(),
+        })
+    }
+}
+
+/// A point on an evolutionary track, tagged with the age of the body at that point.
+#[derive(Debug, Clone, Serialize)]
+pub struct HrTrackPoint {
+    pub age_years: f64,
+    #[serde(flatten)]
+    pub point: HrPoint,
+}
+
+/// The path a single body traces across the diagram as it evolves.
+#[derive(Debug, Clone, Serialize)]
+pub struct HrTrack {
+    pub label: String,
+    pub points: Vec<HrTrackPoint>,
+}
+
+impl HrTrack {
+    pub fn new<T: Evolving>(label: &str, states: &[T]) -> HrTrack {
+        HrTrack {
+            label: label.to_string(),
+            points: states
+                .iter()
+                .filter_map(|state| {
+                    HrPoint::new(state).map(|point| HrTrackPoint {
+                        age_years: state.age().get::<year>(),
+                        point,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Hertzsprung-Russell diagram data for a population of bodies and their evolutionary tracks.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HrDiagram {
+    pub points: Vec<HrPoint>,
+    pub tracks: Vec<HrTrack>,
+}
+
+const CSV_POINT_HEADER: &str =
+    "log_temperature,log_luminosity,absolute_magnitude,bv_index,spectral_type";
+
+/// A CSV field, quoted with any quotes doubled if it holds a comma, quote or line break (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn point_to_csv(point: &HrPoint) -> String {
+    format!(
+        "{},{},{},{},{}",
+        point.log_temperature,
+        point.log_luminosity,
+        point.absolute_magnitude,
+        point.bv_index,
+        point.spectral_type
+    )
+}
+
+impl HrDiagram {
+    /// Plot every body in a population. Bodies that emit no light are left out.
+    pub fn from_population<T: Luminous>(population: &[T]) -> HrDiagram {
+        HrDiagram {
+            points: population.iter().filter_map(HrPoint::new).collect(),
+            tracks: vec![],
+        }
+    }
+
+    pub fn add_track<T: Evolving>(&mut self, label: &str, states: &[T]) {
+        self.tracks.push(HrTrack::new(label, states));
+    }
+
+    /// The population as CSV, one row per body.
+    pub fn to_csv(&self) -> String {
+        self.points
+            .iter()
+            .fold(CSV_POINT_HEADER.to_string(), |csv, point| {
+                format!("{}\n{}", csv, point_to_csv(point))
+            })
+    }
+
+    /// Every evolutionary track as CSV, one row per point, identified by the track label and age.
+    pub fn tracks_to_csv(&self) -> String {
+        self.tracks.iter().fold(
+            format!("track,age_years,{}", CSV_POINT_HEADER),
+            |csv, track| {
+                track.points.iter().fold(csv, |csv, track_point| {
+                    format!(
+                        "{}\n{},{},{}",
+                        csv,
+                        csv_field(&track.label),
+                        track_point.age_years,
+                        point_to_csv(&track_point.point)
+                    )
+                })
+            },
+        )
+    }
+
+    /// The population and tracks as JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
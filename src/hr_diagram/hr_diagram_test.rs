@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::hr_diagram::hr_diagram::{HrDiagram, HrPoint};
+    use crate::protostar::pre_main_sequence::PreMainSequenceStar;
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::solar_mass;
+    use crate::units::units::time::thousand_year;
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, Mass, Time};
+
+    fn zams_stars() -> Vec<PreMainSequenceStar> {
+        [0.5, 1.0, 5.0]
+            .iter()
+            .map(|mass| {
+                PreMainSequenceStar::evolve(
+                    Mass::new::<solar_mass>(*mass),
+                    Length::new::<solar_radius>(0.1),
+                    Time::new::<thousand_year>(100.0),
+                )
+                .unwrap()
+                .states
+                .pop()
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn population_is_plotted() {
+        let diagram = HrDiagram::from_population(&zams_stars());
+        assert_eq!(diagram.points.len(), 3);
+
+        let sun: &HrPoint = &diagram.points[1];
+        assert_approx_eq!(f64, sun.log_luminosity, 0.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, sun.log_temperature, 5778.0_f64.log10(), epsilon = 1e-3);
+        assert_eq!(sun.spectral_type, "G2V");
+
+        // hotter stars are brighter along the main sequence
+        assert!(diagram.points[2].log_temperature > diagram.points[0].log_temperature);
+        assert!(diagram.points[2].absolute_magnitude < diagram.points[0].absolute_magnitude);
+    }
+
+    #[test]
+    fn export_to_csv() {
+        let diagram = HrDiagram::from_population(&zams_stars());
+        let csv = diagram.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "log_temperature,log_luminosity,absolute_magnitude,bv_index,spectral_type"
+        );
+        assert!(lines[2].ends_with(",G2V"));
+    }
+
+    #[test]
+    fn export_tracks() {
+        let track = PreMainSequenceStar::evolve(
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(3.0),
+            Time::new::<thousand_year>(1000.0),
+        )
+        .unwrap();
+        let mut diagram = HrDiagram::default();
+        diagram.add_track("1 solar mass", &track.states);
+
+        assert_eq!(diagram.tracks[0].points.len(), track.states.len());
+        let csv = diagram.tracks_to_csv();
+        assert_eq!(csv.lines().count(), track.states.len() + 1);
+        assert!(csv.lines().nth(1).unwrap().starts_with("1 solar mass,0,"));
+
+        let json: serde_json::Value = serde_json::from_str(&diagram.to_json().unwrap()).unwrap();
+        assert_eq!(json["tracks"][0]["label"], "1 solar mass");
+        assert_eq!(json["tracks"][0]["points"][0]["age_years"], 0.0);
+        assert!(json["tracks"][0]["points"][0]["log_luminosity"].is_number());
+        assert_eq!(json["points"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn export_tracks_with_awkward_labels() {
+        let track = PreMainSequenceStar::evolve(
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(3.0),
+            Time::new::<thousand_year>(1000.0),
+        )
+        .unwrap();
+        let mut diagram = HrDiagram::default();
+        diagram.add_track("1 solar mass, \"slow\" rotator", &track.states);
+
+        let csv = diagram.tracks_to_csv();
+        assert_eq!(csv.lines().count(), track.states.len() + 1);
+        assert!(csv
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("\"1 solar mass, \"\"slow\"\" rotator\",0,"));
+    }
+}
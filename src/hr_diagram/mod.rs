@@ -0,0 +1,2 @@
+pub mod hr_diagram;
+mod hr_diagram_test;
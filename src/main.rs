@@ -9,7 +9,8 @@ use crate::formulae::constants::{
     GRAVITATIONAL_CONSTANT, GRAVITATIONAL_CONSTANT_COLLPASE_ADJUSTMENT,
 };
 use crate::formulae::formulae::{time, volume};
-use crate::gas::gas::{Composition, UniformGas};
+use crate::gas::composition::Composition;
+use crate::gas::gas::UniformGas;
 use crate::transition::transition::Interpolatable;
 use crate::units::units::mass::solar_mass;
 use itertools::Itertools;
@@ -33,10 +34,15 @@ extern crate lazy_static;
 
 mod chemistry;
 mod cloud;
+mod colour;
 mod coordinates;
 mod formulae;
+mod galaxy;
 mod gas;
 mod hash;
+mod hr_diagram;
+mod protostar;
+mod spectral;
 mod transition;
 mod units;
 mod wavelength;
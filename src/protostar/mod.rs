@@ -0,0 +1,4 @@
+pub mod pre_main_sequence;
+mod pre_main_sequence_test;
+pub mod protostar;
+mod protostar_test;
@@ -0,0 +1,163 @@
+use uom::si::f64::{Length, Mass, Power, ThermodynamicTemperature, Time};
+use uom::si::length::meter;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::time::second;
+
+use crate::formulae::constants::GRAVITATIONAL_CONSTANT;
+use crate::formulae::formulae::{
+    area, calculate_luminosity, calculate_temperature, length, power, time,
+};
+use crate::protostar::protostar::Protostar;
+use crate::units::units::mass::solar_mass;
+
+/// The largest fraction of the Kelvin-Helmholtz timescale covered by a single integration step.
+const MAXIMUM_STEP_FRACTION: f64 = 0.01;
+
+/// The stage of contraction a pre-main-sequence star is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreMainSequencePhase {
+    /// Fully convective contraction at a near constant effective temperature. Luminosity falls
+    /// as the star shrinks.
+    Hayashi,
+    /// Radiative contraction at a near constant luminosity. Effective temperature rises as the
+    /// star shrinks.
+    Henyey,
+    /// Contraction has halted as hydrogen fusion has begun in the core.
+    ZeroAgeMainSequence,
+}
+
+/// A snapshot of a star contracting towards the main sequence.
+#[derive(Debug, Clone)]
+pub struct PreMainSequenceStar {
+    /// Time elapsed since accretion ended
+    pub age: Time,
+    pub mass: Mass,
+    pub radius: Length,
+    pub luminosity: Power,
+    pub effective_temperature: ThermodynamicTemperature,
+    pub phase: PreMainSequencePhase,
+}
+
+/// The full contraction of a star from the end of accretion to the zero age main sequence.
+#[derive(Debug, Clone)]
+pub struct PreMainSequenceTrack {
+    pub states: Vec<PreMainSequenceStar>,
+    pub time_to_zams: Time,
+}
+
+impl PreMainSequenceStar {
+    /// The effective temperature a fully convective star of the given mass settles at while it
+    /// contracts down the Hayashi track. Cooler stars would not be in hydrostatic equilibrium.
+    pub fn hayashi_temperature(mass: Mass) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(4100.0 * mass.get::<solar_mass>().powf(0.15))
+    }
+
+    /// Evolve a star from the end of accretion until it settles onto the main sequence, recording
+    /// its state every `time_step`.
+    ///
+    /// The star radiates away gravitational potential energy, contracting on the Kelvin-Helmholtz
+    /// timescale. While fully convective (an n = 3/2 polytrope) it contracts at the Hayashi
+    /// temperature. Once it is as faint as it will be on the main sequence it develops a
+    /// radiative core (an n = 3 polytrope) and heats up at constant luminosity until it reaches
+    /// its main sequence radius.
+    ///
+    /// Returns `None` if `time_step` is not a positive, finite time.
+    pub fn evolve(
+        mass: Mass,
+        initial_radius: Length,
+        time_step: Time,
+    ) -> Option<PreMainSequenceTrack> {
+        if !(time_step.value.is_finite() && time_step.value > 0.0) {
+            return None;
+        }
+
+        let zams_radius = length::main_sequence_radius(mass);
+        let zams_luminosity = power::main_sequence_luminosity(mass);
+        let hayashi_temperature = PreMainSequenceStar::hayashi_temperature(mass);
+
+        let mut age = Time::new::<second>(0.0);
+        let mut radius = initial_radius;
+        let mut states = vec![];
+
+        // the phase of contraction, luminosity and potential energy coefficient at a given radius
+        let contraction_state = |radius: Length| {
+            let hayashi_luminosity = calculate_luminosity(
+                1.0,
+                area::sphere_surface_from_radius(radius),
+                hayashi_temperature,
+            );
+            if hayashi_luminosity > zams_luminosity {
+                (PreMainSequencePhase::Hayashi, hayashi_luminosity, 6.0 / 7.0)
+            } else {
+                (PreMainSequencePhase::Henyey, zams_luminosity, 3.0 / 2.0)
+            }
+        };
+
+        while radius > zams_radius {
+            let (phase, luminosity, _) = contraction_state(radius);
+            states.push(PreMainSequenceStar {
+                age,
+                mass,
+                radius,
+                luminosity,
+                effective_temperature: calculate_temperature(
+                    luminosity,
+                    1.0,
+                    area::sphere_surface_from_radius(radius),
+                ),
+                phase,
+            });
+
+            // sub-step so that no single step shrinks the star by much more than a percent
+            let mut elapsed = Time::new::<second>(0.0);
+            while elapsed < time_step && radius > zams_radius {
+                let (_, luminosity, potential_coefficient) = contraction_state(radius);
+                let kelvin_helmholtz = time::kelvin_helmholtz(mass, radius, luminosity);
+                let step = if time_step - elapsed < kelvin_helmholtz * MAXIMUM_STEP_FRACTION {
+                    time_step - elapsed
+                } else {
+                    kelvin_helmholtz * MAXIMUM_STEP_FRACTION
+                };
+
+                // L = -dE/dt where, by the virial theorem, E = Ω / 2 with Ω = -β G M² / R,
+                // so dR/dt = -2 L R² / (β G M²)
+                let contraction = 2.0 * luminosity.value * radius.value.powi(2)
+                    / (potential_coefficient * GRAVITATIONAL_CONSTANT * mass.value.powi(2));
+                let next_radius = Length::new::<meter>(radius.value - contraction * step.value);
+
+                if next_radius <= zams_radius {
+                    // only count the time taken to reach the main sequence radius
+                    elapsed += step * ((radius - zams_radius).value / (radius - next_radius).value);
+                    radius = zams_radius;
+                } else {
+                    elapsed += step;
+                    radius = next_radius;
+                }
+            }
+            age += elapsed;
+        }
+
+        states.push(PreMainSequenceStar {
+            age,
+            mass,
+            radius: zams_radius,
+            luminosity: zams_luminosity,
+            effective_temperature: calculate_temperature(
+                zams_luminosity,
+                1.0,
+                area::sphere_surface_from_radius(zams_radius),
+            ),
+            phase: PreMainSequencePhase::ZeroAgeMainSequence,
+        });
+
+        Some(PreMainSequenceTrack {
+            states,
+            time_to_zams: age,
+        })
+    }
+
+    /// Continue the evolution of a protostar once it has finished accreting.
+    pub fn from_protostar(protostar: &Protostar, time_step: Time) -> Option<PreMainSequenceTrack> {
+        PreMainSequenceStar::evolve(protostar.mass, protostar.radius, time_step)
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::formulae::constants::{GRAVITATIONAL_CONSTANT, STEFAN_BOLTZMANN_CONSTANT};
+    use crate::formulae::formulae::{length, power, time};
+    use crate::protostar::pre_main_sequence::{PreMainSequencePhase, PreMainSequenceStar};
+    use crate::protostar::protostar::Protostar;
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::solar_mass;
+    use crate::units::units::power::solar_luminosity;
+    use crate::units::units::time::{million_year, thousand_year};
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::PI;
+    use uom::si::f64::{Length, Mass, MassRate, Power, Time};
+    use uom::si::mass_rate::kilogram_per_second;
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    #[test]
+    fn kelvin_helmholtz_timescale_of_sun() {
+        let t = time::kelvin_helmholtz(
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(1.0),
+            Power::new::<solar_luminosity>(1.0),
+        );
+        assert!(t.get::<million_year>() > 30.0 && t.get::<million_year>() < 33.0);
+    }
+
+    #[test]
+    fn main_sequence_relations() {
+        assert_approx_eq!(
+            f64,
+            length::main_sequence_radius(Mass::new::<solar_mass>(1.0)).get::<solar_radius>(),
+            1.0,
+            ulps = 2
+        );
+        assert_approx_eq!(
+            f64,
+            power::main_sequence_luminosity(Mass::new::<solar_mass>(1.0)).get::<solar_luminosity>(),
+            1.0,
+            ulps = 2
+        );
+        assert!(
+            power::main_sequence_luminosity(Mass::new::<solar_mass>(10.0))
+                > power::main_sequence_luminosity(Mass::new::<solar_mass>(2.0))
+        );
+    }
+
+    #[test]
+    fn solar_mass_star_contracts_to_main_sequence() {
+        let track = PreMainSequenceStar::evolve(
+            Mass::new::<solar_mass>(1.0),
+            Length::new::<solar_radius>(3.0),
+            Time::new::<thousand_year>(100.0),
+        )
+        .unwrap();
+
+        // contracting from 3 solar radii takes a little over ten million years, which can be
+        // worked out directly: E = Ω / 2 = -β G M² / 2R, with L = 4πR²σT⁴ at the Hayashi
+        // temperature and then the main sequence luminosity once that is fainter
+        let mass = Mass::new::<solar_mass>(1.0);
+        let energy = |beta: f64| beta * GRAVITATIONAL_CONSTANT * mass.value.powi(2) / 2.0;
+        let hayashi_flux = 4.0
+            * PI
+            * STEFAN_BOLTZMANN_CONSTANT
+            * PreMainSequenceStar::hayashi_temperature(mass).value.powi(4);
+        let zams_luminosity = power::main_sequence_luminosity(mass).value;
+        let turnover = (zams_luminosity / hayashi_flux).sqrt();
+        let hayashi = energy(6.0 / 7.0) / (3.0 * hayashi_flux)
+            * (turnover.powi(-3) - Length::new::<solar_radius>(3.0).value.powi(-3));
+        let henyey = energy(3.0 / 2.0) / zams_luminosity
+            * (1.0 / length::main_sequence_radius(mass).value - 1.0 / turnover);
+        assert_approx_eq!(
+            f64,
+            track.time_to_zams.value / (hayashi + henyey),
+            1.0,
+            epsilon = 0.02
+        );
+        let time_to_zams = track.time_to_zams.get::<million_year>();
+        assert!(time_to_zams > 12.0 && time_to_zams < 14.0);
+
+        let first = track.states.first().unwrap();
+        assert_eq!(first.phase, PreMainSequencePhase::Hayashi);
+        assert_approx_eq!(
+            f64,
+            first.effective_temperature.get::<kelvin>(),
+            PreMainSequenceStar::hayashi_temperature(Mass::new::<solar_mass>(1.0)).get::<kelvin>(),
+            epsilon = 1e-6
+        );
+
+        let last = track.states.last().unwrap();
+        assert_eq!(last.phase, PreMainSequencePhase::ZeroAgeMainSequence);
+        assert_eq!(last.age, track.time_to_zams);
+        assert!(last.effective_temperature.get::<kelvin>() > 5700.0);
+        assert!(last.effective_temperature.get::<kelvin>() < 5850.0);
+
+        assert!(track
+            .states
+            .iter()
+            .any(|state| state.phase == PreMainSequencePhase::Henyey));
+        track.states.windows(2).for_each(|pair| {
+            assert!(pair[1].radius <= pair[0].radius);
+            assert!(pair[1].age > pair[0].age);
+        });
+    }
+
+    #[test]
+    fn contraction_rejects_time_steps_that_never_advance() {
+        for time_step in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(PreMainSequenceStar::evolve(
+                Mass::new::<solar_mass>(1.0),
+                Length::new::<solar_radius>(3.0),
+                Time::new::<thousand_year>(time_step),
+            )
+            .is_none());
+        }
+    }
+
+    #[test]
+    fn low_mass_star_stays_convective() {
+        let track = PreMainSequenceStar::evolve(
+            Mass::new::<solar_mass>(0.2),
+            Length::new::<solar_radius>(2.0),
+            Time::new::<million_year>(1.0),
+        )
+        .unwrap();
+        assert!(track
+            .states
+            .iter()
+            .all(|state| state.phase != PreMainSequencePhase::Henyey));
+    }
+
+    #[test]
+    fn compact_star_is_already_on_main_sequence() {
+        let protostar = Protostar {
+            age: Time::new::<thousand_year>(100.0),
+            mass: Mass::new::<solar_mass>(10.0),
+            radius: Length::new::<solar_radius>(3.0),
+            envelope_mass: Mass::new::<solar_mass>(0.0),
+            accretion_rate: MassRate::new::<kilogram_per_second>(0.0),
+            accretion_luminosity: Power::new::<solar_luminosity>(0.0),
+            accreting: false,
+        };
+        let track =
+            PreMainSequenceStar::from_protostar(&protostar, Time::new::<thousand_year>(1.0))
+                .unwrap();
+        assert_eq!(track.states.len(), 1);
+        assert_eq!(track.time_to_zams.value, 0.0);
+    }
+}
@@ -0,0 +1,83 @@
+use uom::si::f64::{Length, Mass, MassRate, Power, Time};
+use uom::si::mass_rate::kilogram_per_second;
+use uom::si::power::watt;
+use uom::si::time::second;
+
+use crate::formulae::formulae::{mass_rate, power, velocity};
+use crate::gas::gas::UniformGas;
+
+pub struct AccretionOptions {
+    /// The interval between each state in the generated time series
+    pub time_step: Time,
+    /// The fraction of the core's mass that ends up in the protostar before outflows disperse
+    /// the remaining envelope
+    pub star_formation_efficiency: f64,
+    /// The radius of the accreting protostar. Deuterium burning holds young protostars at a
+    /// roughly constant few solar radii while they accrete.
+    pub radius: Length,
+}
+
+/// A single snapshot of a protostar growing at the centre of a collapsing core.
+#[derive(Debug, Clone)]
+pub struct Protostar {
+    /// Time elapsed since the protostar began accreting
+    pub age: Time,
+    pub mass: Mass,
+    pub radius: Length,
+    /// Mass of the core that remains available to accrete
+    pub envelope_mass: Mass,
+    pub accretion_rate: MassRate,
+    pub accretion_luminosity: Power,
+    pub accreting: bool,
+}
+
+impl Protostar {
+    /// Collapse a core of gas into a protostar, producing the state of the protostar at every
+    /// `time_step` until accretion ends.
+    ///
+    /// The core collapses from the inside out, feeding the protostar at a constant rate set by
+    /// the sound speed of the gas (Shu 1977). Accretion ends once the protostar has gathered
+    /// `star_formation_efficiency` of the core's mass, after which the final state is reported
+    /// with no accretion rate or accretion luminosity.
+    ///
+    /// Returns no states if `time_step` is not a positive, finite time.
+    pub fn accrete(core: &UniformGas, options: AccretionOptions) -> Vec<Protostar> {
+        if !(options.time_step.value.is_finite() && options.time_step.value > 0.0) {
+            return vec![];
+        }
+
+        let sound_speed =
+            velocity::isothermal_sound_speed(core.temperature, core.mean_molecular_weight());
+        let accretion_rate = mass_rate::accretion_from_sound_speed(sound_speed);
+        let final_mass = core.mass * options.star_formation_efficiency;
+        let accretion_time = Time::new::<second>(final_mass.value / accretion_rate.value);
+
+        let mut states = vec![];
+        let mut age = Time::new::<second>(0.0);
+        while age < accretion_time {
+            let mass = accretion_rate * age;
+            states.push(Protostar {
+                age,
+                mass,
+                radius: options.radius,
+                envelope_mass: core.mass - mass,
+                accretion_rate,
+                accretion_luminosity: power::from_accretion(mass, accretion_rate, options.radius),
+                accreting: true,
+            });
+            age += options.time_step;
+        }
+
+        states.push(Protostar {
+            age: accretion_time,
+            mass: final_mass,
+            radius: options.radius,
+            envelope_mass: core.mass - final_mass,
+            accretion_rate: MassRate::new::<kilogram_per_second>(0.0),
+            accretion_luminosity: Power::new::<watt>(0.0),
+            accreting: false,
+        });
+
+        states
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::chemistry::molecules::molecules::Molecule;
+    use crate::formulae::formulae::{mass_rate, power, velocity, volume};
+    use crate::gas::composition::Composition;
+    use crate::gas::gas::UniformGas;
+    use crate::protostar::protostar::{AccretionOptions, Protostar};
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::solar_mass;
+    use crate::units::units::mass_rate::solar_mass_per_year;
+    use crate::units::units::power::solar_luminosity;
+    use crate::units::units::time::thousand_year;
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, Mass, MassRate, ThermodynamicTemperature, Time};
+    use uom::si::length::parsec;
+    use uom::si::thermodynamic_temperature::kelvin;
+    use uom::si::velocity::meter_per_second;
+
+    fn dense_core() -> UniformGas {
+        UniformGas::composite_from_vacuum_properties(
+            volume::sphere_volume_from_length(Length::new::<parsec>(0.1)),
+            1.0e11,
+            ThermodynamicTemperature::new::<kelvin>(10.0),
+            Composition(vec![
+                (Molecule::molecular_hydrogen(), 90.0),
+                (Molecule::atomic_helium(), 10.0),
+            ]),
+        )
+    }
+
+    #[test]
+    fn sound_speed_of_cold_molecular_gas() {
+        let cs =
+            velocity::isothermal_sound_speed(ThermodynamicTemperature::new::<kelvin>(10.0), 2.33);
+        assert!(cs.get::<meter_per_second>() > 185.0 && cs.get::<meter_per_second>() < 195.0);
+    }
+
+    #[test]
+    fn accretion_rate_of_cold_core() {
+        // a 10K core should accrete at around 2e-6 solar masses per year
+        let cs =
+            velocity::isothermal_sound_speed(ThermodynamicTemperature::new::<kelvin>(10.0), 2.33);
+        let rate = mass_rate::accretion_from_sound_speed(cs).get::<solar_mass_per_year>();
+        assert!(rate > 1.5e-6 && rate < 2.0e-6);
+    }
+
+    #[test]
+    fn accretion_luminosity_of_solar_protostar() {
+        let luminosity = power::from_accretion(
+            Mass::new::<solar_mass>(1.0),
+            MassRate::new::<solar_mass_per_year>(1.0e-5),
+            Length::new::<solar_radius>(3.0),
+        );
+        assert_approx_eq!(
+            f64,
+            luminosity.get::<solar_luminosity>(),
+            104.0,
+            epsilon = 1.0
+        );
+    }
+
+    #[test]
+    fn protostar_accretes_until_efficiency_reached() {
+        let core = dense_core();
+        let states = Protostar::accrete(
+            &core,
+            AccretionOptions {
+                time_step: Time::new::<thousand_year>(10.0),
+                star_formation_efficiency: 0.3,
+                radius: Length::new::<solar_radius>(3.0),
+            },
+        );
+
+        let first = states.first().unwrap();
+        assert_eq!(first.mass.value, 0.0);
+        assert!(first.accreting);
+
+        let last = states.last().unwrap();
+        assert!(!last.accreting);
+        assert_eq!(last.accretion_luminosity.value, 0.0);
+        assert_approx_eq!(f64, last.mass.value, (core.mass * 0.3).value, ulps = 2);
+        assert_approx_eq!(
+            f64,
+            (last.mass + last.envelope_mass).value,
+            core.mass.value,
+            ulps = 2
+        );
+
+        // the protostar brightens as it grows, as the accretion rate is constant
+        states
+            .iter()
+            .filter(|state| state.accreting)
+            .collect::<Vec<&Protostar>>()
+            .windows(2)
+            .for_each(|pair| {
+                assert!(pair[1].mass > pair[0].mass);
+                assert!(pair[1].accretion_luminosity > pair[0].accretion_luminosity);
+                assert!(pair[1].age > pair[0].age);
+            });
+    }
+
+    #[test]
+    fn protostar_rejects_time_steps_that_never_advance() {
+        let core = dense_core();
+        for time_step in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+            let states = Protostar::accrete(
+                &core,
+                AccretionOptions {
+                    time_step: Time::new::<thousand_year>(time_step),
+                    star_formation_efficiency: 0.3,
+                    radius: Length::new::<solar_radius>(3.0),
+                },
+            );
+            assert!(states.is_empty());
+        }
+    }
+}
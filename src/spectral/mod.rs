@@ -0,0 +1,2 @@
+pub mod spectral;
+mod spectral_test;
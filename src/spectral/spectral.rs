@@ -0,0 +1,322 @@
+use colortemp::RGB;
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uom::si::f64::{Length, Mass, Power, ThermodynamicTemperature};
+use uom::si::length::meter;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::formulae::constants::STEFAN_BOLTZMANN_CONSTANT;
+use crate::formulae::formulae::{area, bv_to_rgb, calculate_bv_index, calculate_luminosity};
+use crate::units::units::length::solar_radius;
+use crate::units::units::mass::solar_mass;
+use crate::units::units::power::solar_luminosity;
+
+/// The Harvard spectral class of a star, from hottest to coolest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpectralClass {
+    O,
+    B,
+    A,
+    F,
+    G,
+    K,
+    M,
+}
+
+/// The Morgan-Keenan luminosity class of a star, from supergiants to dwarfs.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LuminosityClass {
+    /// Supergiants
+    I,
+    /// Bright giants
+    II,
+    /// Giants
+    III,
+    /// Subgiants
+    IV,
+    /// Main sequence dwarfs
+    V,
+}
+
+/// A full MK spectral type such as `G2V` or `M3.5III`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralType {
+    pub class: SpectralClass,
+    /// The decimal subdivision of the class, from 0 (hottest) to 9.5 (coolest)
+    pub subclass: f64,
+    pub luminosity_class: LuminosityClass,
+}
+
+/// The part of a spectral type that was not valid, along with the text or value given for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpectralTypeParseError {
+    Class(String),
+    Subclass(String),
+    LuminosityClass(String),
+}
+
+/// Typical main sequence properties at points along the spectral sequence, as
+/// (sequence position, effective temperature in kelvin, mass in solar masses, radius in solar radii).
+/// The sequence position is ten times the class index plus the subclass, so O0 is 0 and M9 is 69.
+/// Values are approximate and drawn from Pecaut & Mamajek (2013).
+const MAIN_SEQUENCE: [(f64, f64, f64, f64); 17] = [
+    (3.0, 44900.0, 59.0, 13.4),
+    (5.0, 41400.0, 37.0, 11.0),
+    (9.0, 31900.0, 18.0, 7.4),
+    (10.0, 31400.0, 17.7, 7.2),
+    (15.0, 15700.0, 4.0, 2.7),
+    (20.0, 9700.0, 2.2, 1.8),
+    (25.0, 8080.0, 1.8, 1.7),
+    (30.0, 7220.0, 1.6, 1.5),
+    (35.0, 6510.0, 1.3, 1.3),
+    (40.0, 5920.0, 1.06, 1.1),
+    (42.0, 5770.0, 1.0, 1.0),
+    (45.0, 5660.0, 0.93, 0.9),
+    (50.0, 5280.0, 0.88, 0.85),
+    (55.0, 4440.0, 0.7, 0.7),
+    (60.0, 3850.0, 0.57, 0.6),
+    (65.0, 3060.0, 0.16, 0.2),
+    (69.0, 2400.0, 0.08, 0.1),
+];
+
+const CLASSES: [SpectralClass; 7] = [
+    SpectralClass::O,
+    SpectralClass::B,
+    SpectralClass::A,
+    SpectralClass::F,
+    SpectralClass::G,
+    SpectralClass::K,
+    SpectralClass::M,
+];
+
+/// Linearly interpolate a column of the main sequence table by sequence position, clamping at
+/// either end of the table.
+fn interpolate_main_sequence(position: f64, column: fn(&(f64, f64, f64, f64)) -> f64) -> f64 {
+    let first = MAIN_SEQUENCE[0];
+    let last = MAIN_SEQUENCE[MAIN_SEQUENCE.len() - 1];
+    if position <= first.0 {
+        return column(&first);
+    }
+    if position >= last.0 {
+        return column(&last);
+    }
+    let upper = MAIN_SEQUENCE.partition_point(|row| row.0 < position);
+    let (a, b) = (MAIN_SEQUENCE[upper - 1], MAIN_SEQUENCE[upper]);
+    column(&a) + (column(&b) - column(&a)) * (position - a.0) / (b.0 - a.0)
+}
+
+impl SpectralClass {
+    fn index(&self) -> usize {
+        CLASSES.iter().position(|class| class == self).unwrap()
+    }
+}
+
+impl LuminosityClass {
+    /// The typical luminosity, in solar luminosities, of a star of this class given the luminosity
+    /// of a dwarf of the same temperature
+    fn typical_luminosity(&self, dwarf_luminosity: f64) -> f64 {
+        match self {
+            LuminosityClass::I => f64::max(5.0e4, 100.0 * dwarf_luminosity),
+            LuminosityClass::II => f64::max(3.0e3, 20.0 * dwarf_luminosity),
+            LuminosityClass::III => f64::max(1.0e2, 12.0 * dwarf_luminosity),
+            LuminosityClass::IV => 5.0 * dwarf_luminosity,
+            LuminosityClass::V => dwarf_luminosity,
+        }
+    }
+
+    /// Classify a star by its luminosity, in solar luminosities, and the luminosity of a dwarf of
+    /// the same temperature.
+    /// Stars within a magnitude of the main sequence are dwarfs, and within two and a half
+    /// magnitudes are subgiants. Brighter stars are separated by absolute luminosity, as hot
+    /// dwarfs can outshine cool giants.
+    fn from_luminosity(luminosity: f64, dwarf_luminosity: f64) -> LuminosityClass {
+        match luminosity / dwarf_luminosity {
+            ratio if ratio <= 2.5 => LuminosityClass::V,
+            ratio if ratio <= 10.0 => LuminosityClass::IV,
+            _ if luminosity >= f64::max(1.0e4, 30.0 * dwarf_luminosity) => LuminosityClass::I,
+            _ if luminosity >= f64::max(1.0e3, 15.0 * dwarf_luminosity) => LuminosityClass::II,
+            _ => LuminosityClass::III,
+        }
+    }
+}
+
+impl SpectralType {
+    /// The subclass must be at least 0 and below 10, as when parsing.
+    pub fn new(
+        class: SpectralClass,
+        subclass: f64,
+        luminosity_class: LuminosityClass,
+    ) -> Result<SpectralType, SpectralTypeParseError> {
+        if !SpectralType::is_subclass(subclass) {
+            return Err(SpectralTypeParseError::Subclass(subclass.to_string()));
+        }
+        Ok(SpectralType {
+            class,
+            subclass,
+            luminosity_class,
+        })
+    }
+
+    fn is_subclass(subclass: f64) -> bool {
+        (0.0..10.0).contains(&subclass)
+    }
+
+    fn position(&self) -> f64 {
+        self.class.index() as f64 * 10.0 + self.subclass
+    }
+
+    /// The luminosity, in solar luminosities, of a main sequence star at a sequence position
+    fn dwarf_luminosity(position: f64) -> f64 {
+        let radius = Length::new::<solar_radius>(interpolate_main_sequence(position, |row| row.3));
+        let temperature =
+            ThermodynamicTemperature::new::<kelvin>(interpolate_main_sequence(position, |row| {
+                row.1
+            }));
+        calculate_luminosity(1.0, area::sphere_surface_from_radius(radius), temperature)
+            .get::<solar_luminosity>()
+    }
+
+    /// Classify a star from its effective temperature and luminosity.
+    ///
+    /// The class and subclass (to the nearest half) come from the temperature alone. The
+    /// luminosity class compares the star's luminosity with that of a dwarf of the same class.
+    pub fn from_temperature_and_luminosity(
+        temperature: ThermodynamicTemperature,
+        luminosity: Power,
+    ) -> SpectralType {
+        let surface_temperature = temperature.get::<kelvin>();
+        let upper = MAIN_SEQUENCE.partition_point(|row| row.1 > surface_temperature);
+        let position = if upper == 0 {
+            MAIN_SEQUENCE[0].0
+        } else if upper == MAIN_SEQUENCE.len() {
+            MAIN_SEQUENCE[MAIN_SEQUENCE.len() - 1].0
+        } else {
+            // temperatures fall roughly exponentially along the sequence
+            let (a, b) = (MAIN_SEQUENCE[upper - 1], MAIN_SEQUENCE[upper]);
+            a.0 + (b.0 - a.0) * (a.1.ln() - surface_temperature.ln()) / (a.1.ln() - b.1.ln())
+        };
+        let position = (position * 2.0).round() / 2.0;
+        let class_index = usize::min((position / 10.0) as usize, CLASSES.len() - 1);
+
+        let dwarf_luminosity = SpectralType::dwarf_luminosity(position);
+
+        SpectralType {
+            class: CLASSES[class_index],
+            subclass: position - class_index as f64 * 10.0,
+            luminosity_class: LuminosityClass::from_luminosity(
+                luminosity.get::<solar_luminosity>(),
+                dwarf_luminosity,
+            ),
+        }
+    }
+
+    /// The typical effective temperature of a star of this type
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(interpolate_main_sequence(self.position(), |row| {
+            row.1
+        }))
+    }
+
+    /// The typical radius of a star of this type
+    pub fn radius(&self) -> Length {
+        let temperature = self.temperature().value;
+        Length::new::<meter>(f64::sqrt(
+            self.luminosity().value / (4.0 * PI * STEFAN_BOLTZMANN_CONSTANT * temperature.powi(4)),
+        ))
+    }
+
+    /// The typical mass of a star of this type.
+    /// Evolved stars are given at least the lowest mass that can reach that stage of life within
+    /// the age of the universe.
+    pub fn mass(&self) -> Mass {
+        let dwarf = interpolate_main_sequence(self.position(), |row| row.2);
+        Mass::new::<solar_mass>(match self.luminosity_class {
+            LuminosityClass::V => dwarf,
+            LuminosityClass::IV => dwarf * 1.2,
+            LuminosityClass::III => f64::max(dwarf, 1.2),
+            LuminosityClass::II => f64::max(dwarf, 5.0),
+            LuminosityClass::I => f64::max(dwarf, 12.0),
+        })
+    }
+
+    /// The typical luminosity of a star of this type
+    pub fn luminosity(&self) -> Power {
+        let dwarf_luminosity = SpectralType::dwarf_luminosity(self.position());
+        Power::new::<solar_luminosity>(self.luminosity_class.typical_luminosity(dwarf_luminosity))
+    }
+
+    /// The typical colour of a star of this type
+    pub fn colour(&self) -> RGB {
+        bv_to_rgb(calculate_bv_index(self.temperature()))
+    }
+}
+
+impl Display for SpectralClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for LuminosityClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for SpectralType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.class, self.subclass, self.luminosity_class
+        )
+    }
+}
+
+impl FromStr for SpectralType {
+    type Err = SpectralTypeParseError;
+
+    /// Parse a spectral type such as `G2V` or `M3.5III`. A missing luminosity class is taken to
+    /// be a main sequence dwarf.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let class = match s.chars().next() {
+            Some('O') => SpectralClass::O,
+            Some('B') => SpectralClass::B,
+            Some('A') => SpectralClass::A,
+            Some('F') => SpectralClass::F,
+            Some('G') => SpectralClass::G,
+            Some('K') => SpectralClass::K,
+            Some('M') => SpectralClass::M,
+            _ => return Err(SpectralTypeParseError::Class(s.to_string())),
+        };
+
+        let rest = &s[1..];
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (subclass, luminosity_class) = rest.split_at(split);
+
+        let subclass = match subclass.parse::<f64>() {
+            Ok(value) if SpectralType::is_subclass(value) => value,
+            _ => return Err(SpectralTypeParseError::Subclass(s.to_string())),
+        };
+
+        let luminosity_class = match luminosity_class {
+            "I" => LuminosityClass::I,
+            "II" => LuminosityClass::II,
+            "III" => LuminosityClass::III,
+            "IV" => LuminosityClass::IV,
+            "V" | "" => LuminosityClass::V,
+            _ => return Err(SpectralTypeParseError::LuminosityClass(s.to_string())),
+        };
+
+        Ok(SpectralType {
+            class,
+            subclass,
+            luminosity_class,
+        })
+    }
+}
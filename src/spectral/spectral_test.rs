@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::spectral::spectral::{
+        LuminosityClass, SpectralClass, SpectralType, SpectralTypeParseError,
+    };
+    use crate::units::units::length::solar_radius;
+    use crate::units::units::mass::solar_mass;
+    use crate::units::units::power::solar_luminosity;
+    use float_cmp::assert_approx_eq;
+    use std::str::FromStr;
+    use uom::si::f64::{Power, ThermodynamicTemperature};
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    #[test]
+    fn parse_spectral_types() {
+        assert_eq!(
+            SpectralType::from_str("G2V").unwrap(),
+            SpectralType::new(SpectralClass::G, 2.0, LuminosityClass::V).unwrap()
+        );
+        assert_eq!(
+            SpectralType::from_str("M3.5III").unwrap(),
+            SpectralType::new(SpectralClass::M, 3.5, LuminosityClass::III).unwrap()
+        );
+        assert_eq!(
+            SpectralType::from_str("B0").unwrap(),
+            SpectralType::new(SpectralClass::B, 0.0, LuminosityClass::V).unwrap()
+        );
+        assert_eq!(
+            SpectralType::from_str("O9.5I").unwrap(),
+            SpectralType::new(SpectralClass::O, 9.5, LuminosityClass::I).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_spectral_types() {
+        assert!(matches!(
+            SpectralType::from_str("X2V"),
+            Err(SpectralTypeParseError::Class(_))
+        ));
+        assert!(matches!(
+            SpectralType::from_str("GV"),
+            Err(SpectralTypeParseError::Subclass(_))
+        ));
+        assert!(matches!(
+            SpectralType::from_str("G12V"),
+            Err(SpectralTypeParseError::Subclass(_))
+        ));
+        assert!(matches!(
+            SpectralType::from_str("G2VII"),
+            Err(SpectralTypeParseError::LuminosityClass(_))
+        ));
+    }
+
+    #[test]
+    fn new_rejects_invalid_subclasses() {
+        for subclass in [-0.5, 10.0, 12.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                SpectralType::new(SpectralClass::G, subclass, LuminosityClass::V),
+                Err(SpectralTypeParseError::Subclass(_))
+            ));
+        }
+        assert!(SpectralType::new(SpectralClass::G, 9.5, LuminosityClass::V).is_ok());
+    }
+
+    #[test]
+    fn display_spectral_types() {
+        assert_eq!(
+            SpectralType::new(SpectralClass::G, 2.0, LuminosityClass::V)
+                .unwrap()
+                .to_string(),
+            "G2V"
+        );
+        assert_eq!(
+            SpectralType::new(SpectralClass::M, 3.5, LuminosityClass::III)
+                .unwrap()
+                .to_string(),
+            "M3.5III"
+        );
+        ["O5V", "B3IV", "A0I", "F5II", "K1.5III"]
+            .iter()
+            .for_each(|s| {
+                assert_eq!(SpectralType::from_str(s).unwrap().to_string(), *s);
+            });
+    }
+
+    #[test]
+    fn classify_the_sun() {
+        let sun = SpectralType::from_temperature_and_luminosity(
+            ThermodynamicTemperature::new::<kelvin>(5778.0),
+            Power::new::<solar_luminosity>(1.0),
+        );
+        assert_eq!(sun.to_string(), "G2V");
+    }
+
+    #[test]
+    fn classify_giants() {
+        // Aldebaran is a K5III giant, roughly 44 solar radii across
+        let aldebaran = SpectralType::from_temperature_and_luminosity(
+            ThermodynamicTemperature::new::<kelvin>(3900.0),
+            Power::new::<solar_luminosity>(439.0),
+        );
+        assert_eq!(aldebaran.class, SpectralClass::K);
+        assert_eq!(aldebaran.luminosity_class, LuminosityClass::III);
+
+        // Betelgeuse is an M1-2 supergiant
+        let betelgeuse = SpectralType::from_temperature_and_luminosity(
+            ThermodynamicTemperature::new::<kelvin>(3600.0),
+            Power::new::<solar_luminosity>(100000.0),
+        );
+        assert_eq!(betelgeuse.class, SpectralClass::M);
+        assert_eq!(betelgeuse.luminosity_class, LuminosityClass::I);
+
+        // Rigel is a hot B8 supergiant
+        let rigel = SpectralType::from_temperature_and_luminosity(
+            ThermodynamicTemperature::new::<kelvin>(12100.0),
+            Power::new::<solar_luminosity>(120000.0),
+        );
+        assert_eq!(rigel.class, SpectralClass::B);
+        assert_eq!(rigel.luminosity_class, LuminosityClass::I);
+    }
+
+    #[test]
+    fn typical_properties_of_types() {
+        let sun = SpectralType::from_str("G2V").unwrap();
+        assert_approx_eq!(f64, sun.temperature().get::<kelvin>(), 5770.0, ulps = 2);
+        assert_approx_eq!(f64, sun.mass().get::<solar_mass>(), 1.0, ulps = 2);
+        assert_approx_eq!(f64, sun.radius().get::<solar_radius>(), 1.0, ulps = 2);
+        assert_approx_eq!(
+            f64,
+            sun.luminosity().get::<solar_luminosity>(),
+            1.0,
+            epsilon = 0.01
+        );
+
+        let giant = SpectralType::from_str("G2III").unwrap();
+        assert!(giant.radius() > sun.radius());
+        assert!(giant.luminosity() > sun.luminosity());
+
+        let hot = SpectralType::from_str("B0V").unwrap();
+        let cool = SpectralType::from_str("M5V").unwrap();
+        assert!(hot.temperature() > sun.temperature() && sun.temperature() > cool.temperature());
+        assert!(hot.colour().b > cool.colour().b);
+        assert!(hot.colour().r < cool.colour().r || hot.colour().g < cool.colour().g);
+    }
+
+    #[test]
+    fn round_trip_typical_properties() {
+        [
+            "B5V", "A0V", "F5V", "G2V", "K5V", "M0V", "B0IV", "G2III", "K5III", "M3.5III", "F5II",
+            "B5I", "M2I",
+        ]
+        .iter()
+        .for_each(|s| {
+            let spectral_type = SpectralType::from_str(s).unwrap();
+            let derived = SpectralType::from_temperature_and_luminosity(
+                spectral_type.temperature(),
+                spectral_type.luminosity(),
+            );
+            assert_eq!(derived, spectral_type);
+        });
+    }
+}
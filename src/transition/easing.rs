@@ -0,0 +1,340 @@
+//! A catalogue of easing functions for use with `Interpolatable`.
+//!
+//! Every function maps a transition in `[0, 1]` to an eased transition that starts at 0 and
+//! finishes at 1. The `back` and `elastic` families overshoot that range on the way.
+//! Curves follow the usual definitions from Robert Penner's easing equations.
+
+use std::f64::consts::PI;
+
+use crate::transition::transition::{EasingFunction, Interpolatable};
+
+/// How far the `back` family pulls back before moving, Penner's classic 10% overshoot.
+const BACK_OVERSHOOT: f64 = 1.701_58;
+
+pub fn linear(t: f64) -> f64 {
+    t
+}
+
+pub fn quad_in(t: f64) -> f64 {
+    t * t
+}
+
+pub fn quad_out(t: f64) -> f64 {
+    out(quad_in, t)
+}
+
+pub fn quad_in_out(t: f64) -> f64 {
+    in_out(quad_in, t)
+}
+
+pub fn cubic_in(t: f64) -> f64 {
+    t.powi(3)
+}
+
+pub fn cubic_out(t: f64) -> f64 {
+    out(cubic_in, t)
+}
+
+pub fn cubic_in_out(t: f64) -> f64 {
+    in_out(cubic_in, t)
+}
+
+pub fn quart_in(t: f64) -> f64 {
+    t.powi(4)
+}
+
+pub fn quart_out(t: f64) -> f64 {
+    out(quart_in, t)
+}
+
+pub fn quart_in_out(t: f64) -> f64 {
+    in_out(quart_in, t)
+}
+
+pub fn quint_in(t: f64) -> f64 {
+    t.powi(5)
+}
+
+pub fn quint_out(t: f64) -> f64 {
+    out(quint_in, t)
+}
+
+pub fn quint_in_out(t: f64) -> f64 {
+    in_out(quint_in, t)
+}
+
+pub fn sine_in(t: f64) -> f64 {
+    1.0 - (t * PI / 2.0).cos()
+}
+
+pub fn sine_out(t: f64) -> f64 {
+    out(sine_in, t)
+}
+
+pub fn sine_in_out(t: f64) -> f64 {
+    in_out(sine_in, t)
+}
+
+pub fn expo_in(t: f64) -> f64 {
+    // 2^(10(t - 1)) never quite reaches zero, so pin the start
+    if t <= 0.0 {
+        0.0
+    } else {
+        2.0_f64.powf(10.0 * (t - 1.0))
+    }
+}
+
+pub fn expo_out(t: f64) -> f64 {
+    out(expo_in, t)
+}
+
+pub fn expo_in_out(t: f64) -> f64 {
+    in_out(expo_in, t)
+}
+
+pub fn circ_in(t: f64) -> f64 {
+    1.0 - (1.0 - t * t).max(0.0).sqrt()
+}
+
+pub fn circ_out(t: f64) -> f64 {
+    out(circ_in, t)
+}
+
+pub fn circ_in_out(t: f64) -> f64 {
+    in_out(circ_in, t)
+}
+
+pub fn back_in(t: f64) -> f64 {
+    t * t * ((BACK_OVERSHOOT + 1.0) * t - BACK_OVERSHOOT)
+}
+
+pub fn back_out(t: f64) -> f64 {
+    out(back_in, t)
+}
+
+pub fn back_in_out(t: f64) -> f64 {
+    in_out(back_in, t)
+}
+
+pub fn elastic_in(t: f64) -> f64 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+    // a decaying sine with a period of 0.3 of the transition
+    -(2.0_f64.powf(10.0 * (t - 1.0))) * ((t - 1.075) * 2.0 * PI / 0.3).sin()
+}
+
+pub fn elastic_out(t: f64) -> f64 {
+    out(elastic_in, t)
+}
+
+pub fn elastic_in_out(t: f64) -> f64 {
+    in_out(elastic_in, t)
+}
+
+pub fn bounce_out(t: f64) -> f64 {
+    // four parabolic bounces, each a quarter the height of the last
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
+
+pub fn bounce_in(t: f64) -> f64 {
+    out(bounce_out, t)
+}
+
+pub fn bounce_in_out(t: f64) -> f64 {
+    in_out(bounce_in, t)
+}
+
+/// Hermite smoothing, `3t² - 2t³`, with zero slope at both ends
+pub fn smoothstep(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Ken Perlin's smootherstep, `6t⁵ - 15t⁴ + 10t³`, with zero slope and curvature at both ends
+pub fn smootherstep(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// The CSS `ease` timing function
+pub fn ease(t: f64) -> f64 {
+    CubicBezier::EASE.ease(t)
+}
+
+/// The CSS `ease-in` timing function
+pub fn ease_in(t: f64) -> f64 {
+    CubicBezier::EASE_IN.ease(t)
+}
+
+/// The CSS `ease-out` timing function
+pub fn ease_out(t: f64) -> f64 {
+    CubicBezier::EASE_OUT.ease(t)
+}
+
+/// The CSS `ease-in-out` timing function
+pub fn ease_in_out(t: f64) -> f64 {
+    CubicBezier::EASE_IN_OUT.ease(t)
+}
+
+/// Mirror an ease-in curve into an ease-out curve
+fn out(ease_in: EasingFunction, t: f64) -> f64 {
+    1.0 - ease_in(1.0 - t)
+}
+
+/// Ease in over the first half of the transition and out over the second
+fn in_out(ease_in: EasingFunction, t: f64) -> f64 {
+    if t < 0.5 {
+        ease_in(2.0 * t) / 2.0
+    } else {
+        1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+    }
+}
+
+/// A cubic Bézier timing curve from (0, 0) to (1, 1) through two control points, as used by
+/// CSS transitions. The x coordinate of each control point must lie within `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl CubicBezier {
+    pub const EASE: CubicBezier = CubicBezier::new(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: CubicBezier = CubicBezier::new(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: CubicBezier = CubicBezier::new(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: CubicBezier = CubicBezier::new(0.42, 0.0, 0.58, 1.0);
+
+    pub const fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> CubicBezier {
+        CubicBezier { x1, y1, x2, y2 }
+    }
+
+    fn sample(a: f64, b: f64, s: f64) -> f64 {
+        // B(s) = 3(1-s)²s a + 3(1-s)s² b + s³
+        let inverse = 1.0 - s;
+        3.0 * inverse * inverse * s * a + 3.0 * inverse * s * s * b + s * s * s
+    }
+
+    fn slope(a: f64, b: f64, s: f64) -> f64 {
+        let inverse = 1.0 - s;
+        3.0 * inverse * inverse * a + 6.0 * inverse * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    }
+
+    /// The eased value at transition `t`, found by solving the curve's x for `t` and reading
+    /// off its y.
+    pub fn ease(&self, t: f64) -> f64 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        // Newton's method converges in a few steps on all but the flattest curves
+        let mut s = t;
+        for _ in 0..8 {
+            let error = CubicBezier::sample(self.x1, self.x2, s) - t;
+            if error.abs() < 1e-12 {
+                return CubicBezier::sample(self.y1, self.y2, s);
+            }
+            let slope = CubicBezier::slope(self.x1, self.x2, s);
+            if slope.abs() < 1e-9 {
+                break;
+            }
+            s -= error / slope;
+        }
+
+        // otherwise fall back to bisection, which always converges as x is monotonic
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+        for _ in 0..64 {
+            let x = CubicBezier::sample(self.x1, self.x2, s);
+            if (x - t).abs() < 1e-12 {
+                break;
+            }
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.0;
+        }
+        CubicBezier::sample(self.y1, self.y2, s)
+    }
+
+    /// Interpolate `from` towards `to` along this curve.
+    ///
+    /// An `EasingFunction` can't carry control points, so this eases the whole transition before
+    /// handing it on. Values delayed by an offset therefore run over the tail of the curve, not
+    /// the whole of it as they would with an `EasingFunction`.
+    pub fn interpolate<T: Interpolatable>(
+        &self,
+        from: &T,
+        to: &T,
+        transition: f64,
+        asynchronous_options: Option<T::AO>,
+    ) -> T {
+        from.interpolate(to, self.ease(transition), None, asynchronous_options)
+    }
+}
+
+/// Every named easing function, for looking up easings from data files.
+pub const CATALOGUE: [(&str, EasingFunction); 37] = [
+    ("linear", linear),
+    ("quad_in", quad_in),
+    ("quad_out", quad_out),
+    ("quad_in_out", quad_in_out),
+    ("cubic_in", cubic_in),
+    ("cubic_out", cubic_out),
+    ("cubic_in_out", cubic_in_out),
+    ("quart_in", quart_in),
+    ("quart_out", quart_out),
+    ("quart_in_out", quart_in_out),
+    ("quint_in", quint_in),
+    ("quint_out", quint_out),
+    ("quint_in_out", quint_in_out),
+    ("sine_in", sine_in),
+    ("sine_out", sine_out),
+    ("sine_in_out", sine_in_out),
+    ("expo_in", expo_in),
+    ("expo_out", expo_out),
+    ("expo_in_out", expo_in_out),
+    ("circ_in", circ_in),
+    ("circ_out", circ_out),
+    ("circ_in_out", circ_in_out),
+    ("back_in", back_in),
+    ("back_out", back_out),
+    ("back_in_out", back_in_out),
+    ("elastic_in", elastic_in),
+    ("elastic_out", elastic_out),
+    ("elastic_in_out", elastic_in_out),
+    ("bounce_in", bounce_in),
+    ("bounce_out", bounce_out),
+    ("bounce_in_out", bounce_in_out),
+    ("smoothstep", smoothstep),
+    ("smootherstep", smootherstep),
+    ("ease", ease),
+    ("ease_in", ease_in),
+    ("ease_out", ease_out),
+    ("ease_in_out", ease_in_out),
+];
+
+/// Look up an easing function by its name in the catalogue
+pub fn by_name(name: &str) -> Option<EasingFunction> {
+    CATALOGUE
+        .iter()
+        .find(|(entry, _)| *entry == name)
+        .map(|(_, function)| *function)
+}
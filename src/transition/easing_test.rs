@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::transition::easing::{
+        back_in, bounce_out, by_name, cubic_in, cubic_in_out, cubic_out, ease, elastic_out, linear,
+        quad_in, smoothstep, CubicBezier, CATALOGUE,
+    };
+    use crate::transition::transition::{Interpolatable, Interpolationf64AsyncOptions};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn easing_catalogue_endpoints() {
+        for (name, function) in CATALOGUE {
+            assert_approx_eq!(f64, function(0.0), 0.0, epsilon = 1e-9);
+            assert_approx_eq!(f64, function(1.0), 1.0, epsilon = 1e-9);
+            assert_eq!(by_name(name).unwrap()(0.25), function(0.25), "{}", name);
+        }
+        assert!(by_name("wobble").is_none());
+    }
+
+    #[test]
+    fn easing_catalogue_is_continuous() {
+        for (name, function) in CATALOGUE {
+            // no jumps bigger than the elastic curves' steepest swing
+            for step in 0..1000 {
+                let t = step as f64 / 1000.0;
+                let jump = (function(t + 0.001) - function(t)).abs();
+                assert!(jump < 0.05, "{} jumps at {}", name, t);
+            }
+        }
+    }
+
+    #[test]
+    fn easing_shapes() {
+        assert_eq!(quad_in(0.5), 0.25);
+        assert_eq!(cubic_out(0.5), 0.875);
+        assert_eq!(cubic_in_out(0.5), 0.5);
+        assert_approx_eq!(
+            f64,
+            cubic_in_out(0.25),
+            cubic_in(0.5) / 2.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(smoothstep(0.5), 0.5);
+        assert_eq!(linear(0.3), 0.3);
+
+        // back pulls back below zero before heading to one
+        assert!(back_in(0.2) < 0.0);
+        // elastic overshoots one before settling
+        assert!((0..100).any(|i| elastic_out(i as f64 / 100.0) > 1.0));
+        // bounce touches down at its first bounce
+        assert_approx_eq!(f64, bounce_out(1.0 / 2.75), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn easing_cubic_bezier() {
+        let straight = CubicBezier::new(0.0, 0.0, 1.0, 1.0);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_approx_eq!(f64, straight.ease(t), t, epsilon = 1e-9);
+        }
+
+        // reference value from browser implementations of the CSS `ease` curve
+        assert_approx_eq!(f64, ease(0.5), 0.8024033877399112, epsilon = 1e-6);
+
+        // flat ends, where Newton's method alone would stall
+        let steep = CubicBezier::new(1.0, 0.0, 0.0, 1.0);
+        assert_approx_eq!(f64, steep.ease(0.5), 0.5, epsilon = 1e-9);
+        assert!(steep.ease(0.01) < 0.01);
+    }
+
+    #[test]
+    fn easing_with_custom_bezier() {
+        // overshoots past the target before settling back
+        let curve = CubicBezier::new(0.3, 0.0, 0.4, 1.6);
+        for t in [0.0, 0.25, 0.5, 0.8, 1.0] {
+            let value = curve.interpolate(&10.0, &20.0, t, None);
+            assert_approx_eq!(f64, value, 10.0 + 10.0 * curve.ease(t), epsilon = 1e-12);
+        }
+        assert!(curve.interpolate(&10.0, &20.0, 0.8, None) > 20.0);
+        assert_eq!(curve.interpolate(&10.0, &20.0, 1.0, None), 20.0);
+
+        let pair = curve.interpolate(&(0.0, 100.0), &(1.0, 0.0), 0.5, None);
+        assert_approx_eq!(f64, pair.0, curve.ease(0.5), epsilon = 1e-12);
+        assert_approx_eq!(f64, pair.1, 100.0 * (1.0 - curve.ease(0.5)), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn easing_with_offset() {
+        // an offset of 0.5 holds at the start until half way, then runs the full range
+        let offset = |t: f64| f64::offset_transition(t, 0.5);
+        assert_eq!(offset(0.0), 0.0);
+        assert_eq!(offset(0.5), 0.0);
+        assert_eq!(offset(0.75), 0.5);
+        assert_eq!(offset(1.0), 1.0);
+
+        // the whole run is delayed to the end, but still finishes there
+        let last = |t: f64| f64::offset_transition(t, 1.0);
+        assert_eq!(last(0.0), 0.0);
+        assert_eq!(last(0.99), 0.0);
+        assert_eq!(last(1.0), 1.0);
+        let value = 1.0.interpolate(&101.0, 1.0, None, Some(Interpolationf64AsyncOptions(1.0)));
+        assert_eq!(value, 101.0);
+
+        let value = 1.0.interpolate(&101.0, 0.75, Some(quad_in), None);
+        assert_eq!(value, 1.0 + 100.0 * 0.5625);
+    }
+}
@@ -1,2 +1,6 @@
+pub mod easing;
+mod easing_test;
+pub mod timeline;
+mod timeline_test;
 pub mod transition;
 mod transition_test;
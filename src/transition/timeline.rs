@@ -0,0 +1,156 @@
+use uom::si::f64::Time;
+
+use crate::transition::transition::{EasingFunction, Interpolatable};
+
+/// How a timeline fills the time between its keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineInterpolation {
+    /// Hold each keyframe until the next one
+    Step,
+    /// Blend straight from each keyframe to the next
+    Linear,
+    /// A Catmull-Rom spline through every keyframe, knotted at the keyframe times, so that
+    /// changes in rate are smooth across keyframes for values that extrapolate linearly
+    CatmullRom,
+}
+
+/// A value at a point in time. `ease` shapes the segment from this keyframe to the next.
+#[derive(Debug, Clone)]
+pub struct Keyframe<T> {
+    pub time: Time,
+    pub value: T,
+    pub ease: Option<EasingFunction>,
+}
+
+/// Time-stamped keyframes, such as the states of successive collapse steps, that can be sampled
+/// at any time in between. Sampling before the first or after the last keyframe holds its value.
+#[derive(Debug, Clone)]
+pub struct Timeline<T: Interpolatable> {
+    pub interpolation: TimelineInterpolation,
+    keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Interpolatable + Clone> Timeline<T> {
+    pub fn new(interpolation: TimelineInterpolation) -> Timeline<T> {
+        Timeline {
+            interpolation,
+            keyframes: vec![],
+        }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    pub fn start(&self) -> Option<Time> {
+        self.keyframes.first().map(|keyframe| keyframe.time)
+    }
+
+    pub fn end(&self) -> Option<Time> {
+        self.keyframes.last().map(|keyframe| keyframe.time)
+    }
+
+    pub fn insert(&mut self, time: Time, value: T) -> bool {
+        self.insert_eased(time, value, None)
+    }
+
+    /// Add a keyframe whose following segment is eased. A keyframe already at `time` is replaced.
+    /// Returns `false`, leaving the timeline as it was, if `time` is not finite.
+    pub fn insert_eased(&mut self, time: Time, value: T, ease: Option<EasingFunction>) -> bool {
+        if !time.value.is_finite() {
+            return false;
+        }
+        let keyframe = Keyframe { time, value, ease };
+        match self
+            .keyframes
+            .binary_search_by(|other| other.time.value.total_cmp(&time.value))
+        {
+            Ok(index) => self.keyframes[index] = keyframe,
+            Err(index) => self.keyframes.insert(index, keyframe),
+        }
+        true
+    }
+
+    pub fn remove(&mut self, time: Time) -> Option<Keyframe<T>> {
+        let index = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time == time)?;
+        Some(self.keyframes.remove(index))
+    }
+
+    /// The value at `time`, or `None` if the timeline has no keyframes or `time` is not finite
+    pub fn sample(&self, time: Time) -> Option<T> {
+        if !time.value.is_finite() {
+            return None;
+        }
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        if time <= first.time {
+            return Some(first.value.clone());
+        }
+        if time >= last.time {
+            return Some(last.value.clone());
+        }
+
+        // the segment starting at the last keyframe at or before `time`
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time)
+            - 1;
+        let (from, to) = (&self.keyframes[index], &self.keyframes[index + 1]);
+        let local = ((time - from.time) / (to.time - from.time)).value;
+        let eased = from.ease.map_or(local, |ease| ease(local));
+
+        Some(match self.interpolation {
+            TimelineInterpolation::Step => from.value.clone(),
+            TimelineInterpolation::Linear => from.value.interpolate(&to.value, eased, None, None),
+            TimelineInterpolation::CatmullRom => self.catmull_rom(index, eased),
+        })
+    }
+
+    /// Evaluate the spline over the segment starting at keyframe `index`, at fraction `local`
+    /// of the way along it.
+    ///
+    /// Uses the Barry-Goldman pyramid, which builds the spline from nothing but repeated
+    /// interpolation, some of it at factors outside 0 to 1. It is only smooth for types that
+    /// extrapolate linearly, such as numbers and quantities; `Option`, `HashMap` and fields marked
+    /// `#[interpolatable(offset)]` clamp or switch at those factors instead. Past either end of
+    /// the timeline a phantom keyframe repeats the end value one segment further out.
+    fn catmull_rom(&self, index: usize, local: f64) -> T {
+        let knot = |index: usize| {
+            let keyframe = &self.keyframes[index];
+            (keyframe.time.value, &keyframe.value)
+        };
+        let (t1, p1) = knot(index);
+        let (t2, p2) = knot(index + 1);
+        let (t0, p0) = match index {
+            0 => (2.0 * t1 - t2, p1),
+            _ => knot(index - 1),
+        };
+        let (t3, p3) = if index + 2 < self.keyframes.len() {
+            knot(index + 2)
+        } else {
+            (2.0 * t2 - t1, p2)
+        };
+
+        let t = t1 + (t2 - t1) * local;
+        let blend =
+            |a: &T, ta: f64, b: &T, tb: f64| a.interpolate(b, (t - ta) / (tb - ta), None, None);
+
+        let a1 = blend(p0, t0, p1, t1);
+        let a2 = blend(p1, t1, p2, t2);
+        let a3 = blend(p2, t2, p3, t3);
+        let b1 = blend(&a1, t0, &a2, t2);
+        let b2 = blend(&a2, t1, &a3, t3);
+        blend(&b1, t1, &b2, t2)
+    }
+}
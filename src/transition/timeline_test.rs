@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use crate::transition::easing::quad_in;
+    use crate::transition::timeline::{Timeline, TimelineInterpolation};
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, Time};
+    use uom::si::length::light_year;
+    use uom::si::time::{second, year};
+
+    fn seconds(value: f64) -> Time {
+        Time::new::<second>(value)
+    }
+
+    fn timeline(interpolation: TimelineInterpolation, points: &[(f64, f64)]) -> Timeline<f64> {
+        let mut timeline = Timeline::new(interpolation);
+        points.iter().for_each(|(time, value)| {
+            timeline.insert(seconds(*time), *value);
+        });
+        timeline
+    }
+
+    #[test]
+    fn timeline_linear() {
+        // keyframes can be added out of order
+        let timeline = timeline(
+            TimelineInterpolation::Linear,
+            &[(10.0, 100.0), (0.0, 0.0), (20.0, 50.0)],
+        );
+        assert_eq!(timeline.start(), Some(seconds(0.0)));
+        assert_eq!(timeline.end(), Some(seconds(20.0)));
+        assert_eq!(timeline.sample(seconds(5.0)), Some(50.0));
+        assert_eq!(timeline.sample(seconds(10.0)), Some(100.0));
+        assert_eq!(timeline.sample(seconds(15.0)), Some(75.0));
+
+        // held beyond either end
+        assert_eq!(timeline.sample(seconds(-3.0)), Some(0.0));
+        assert_eq!(timeline.sample(seconds(30.0)), Some(50.0));
+
+        assert_eq!(
+            Timeline::<f64>::new(TimelineInterpolation::Linear).sample(seconds(0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn timeline_rejects_times_that_are_not_finite() {
+        for interpolation in [
+            TimelineInterpolation::Step,
+            TimelineInterpolation::Linear,
+            TimelineInterpolation::CatmullRom,
+        ] {
+            let timeline = timeline(interpolation, &[(0.0, 0.0), (10.0, 1.0), (20.0, 4.0)]);
+            for time in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                assert_eq!(timeline.sample(seconds(time)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn timeline_ignores_keyframes_at_times_that_are_not_finite() {
+        let mut timeline = timeline(
+            TimelineInterpolation::CatmullRom,
+            &[(0.0, 0.0), (10.0, 1.0)],
+        );
+        for time in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(!timeline.insert(seconds(time), 5.0));
+        }
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline.end(), Some(seconds(10.0)));
+        assert!(timeline.sample(seconds(9.0)).unwrap().is_finite());
+    }
+
+    #[test]
+    fn timeline_step_and_replace() {
+        let mut timeline = timeline(TimelineInterpolation::Step, &[(0.0, 1.0), (10.0, 2.0)]);
+        assert_eq!(timeline.sample(seconds(9.9)), Some(1.0));
+        assert_eq!(timeline.sample(seconds(10.0)), Some(2.0));
+
+        timeline.insert(seconds(0.0), 5.0);
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline.sample(seconds(5.0)), Some(5.0));
+
+        assert!(timeline.remove(seconds(10.0)).is_some());
+        assert!(timeline.remove(seconds(10.0)).is_none());
+        assert_eq!(timeline.len(), 1);
+    }
+
+    #[test]
+    fn timeline_per_segment_easing() {
+        let mut timeline = Timeline::new(TimelineInterpolation::Linear);
+        timeline.insert_eased(seconds(0.0), 0.0, Some(quad_in));
+        timeline.insert(seconds(10.0), 100.0);
+        timeline.insert(seconds(20.0), 200.0);
+
+        assert_eq!(timeline.sample(seconds(5.0)), Some(25.0));
+        // the second segment has no easing of its own
+        assert_eq!(timeline.sample(seconds(15.0)), Some(150.0));
+    }
+
+    #[test]
+    fn timeline_catmull_rom() {
+        let points = [(0.0, 0.0), (1.0, 3.0), (3.0, -1.0), (4.0, 2.0), (6.0, 2.0)];
+        let spline = timeline(TimelineInterpolation::CatmullRom, &points);
+
+        // passes through every keyframe
+        for (time, value) in points {
+            assert_approx_eq!(
+                f64,
+                spline.sample(seconds(time)).unwrap(),
+                value,
+                epsilon = 1e-9
+            );
+        }
+
+        // and its rate of change is continuous across them
+        let rate = |time: f64| {
+            let step = 1e-6;
+            (spline.sample(seconds(time + step)).unwrap()
+                - spline.sample(seconds(time - step)).unwrap())
+                / (2.0 * step)
+        };
+        for (time, _) in &points[1..4] {
+            let (before, after) = (rate(time - 1e-3), rate(time + 1e-3));
+            assert_approx_eq!(f64, before, after, epsilon = 0.05);
+        }
+
+        // straight, evenly spaced keyframes give a straight line
+        let straight = timeline(
+            TimelineInterpolation::CatmullRom,
+            &[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)],
+        );
+        assert_approx_eq!(
+            f64,
+            straight.sample(seconds(1.5)).unwrap(),
+            1.5,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn timeline_of_quantities() {
+        let mut timeline = Timeline::new(TimelineInterpolation::CatmullRom);
+        timeline.insert(Time::new::<year>(0.0), Length::new::<light_year>(10.0));
+        timeline.insert(Time::new::<year>(1000.0), Length::new::<light_year>(8.0));
+        timeline.insert(Time::new::<year>(2000.0), Length::new::<light_year>(4.0));
+
+        let radius = timeline.sample(Time::new::<year>(1500.0)).unwrap();
+        assert!(radius < Length::new::<light_year>(8.0));
+        assert!(radius > Length::new::<light_year>(4.0));
+    }
+}
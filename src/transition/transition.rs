@@ -2,6 +2,9 @@ use arrayvec::ArrayVec;
 use num::{Num, PrimInt};
 use std::any::Any;
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use uom::si::{Dimension, Quantity, Units};
 
 pub type EasingFunction = fn(f64) -> f64;
 
@@ -15,6 +18,24 @@ pub trait Interpolatable {
         asynchronous_options: Option<Self::AO>,
     ) -> Self;
 
+    /// Interpolate with values blended in `space` rather than linearly. In the linear space this
+    /// must match `interpolate`. Types that have nothing to blend on a scale, or haven't been
+    /// taught how, ignore the space.
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = space;
+        self.interpolate(other, transition, ease, asynchronous_options)
+    }
+
     fn lerp(value_a: f64, value_b: f64, transition: f64, ease: Option<EasingFunction>) -> f64 {
         match ease {
             None => value_a + (value_b - value_a) * transition,
@@ -22,16 +43,79 @@ pub trait Interpolatable {
         }
     }
 
+    /// Delay a transition so that it holds at 0 until `offset`, then runs from 0 to 1 over the
+    /// remainder. Easing is applied to the remapped transition, so a delayed value still eases
+    /// over its whole, shorter, run and finishes with everything else.
     fn offset_transition(transition: f64, offset: f64) -> f64 {
-        if transition >= offset {
-            transition * transition * transition
-        } else {
-            0.0
+        remap_transition(transition, offset, 1.0)
+    }
+}
+
+/// Map the window `[start, end]` of a transition onto `[0, 1]`, holding at 0 before the window
+/// and at 1 after it. An empty window jumps straight from 0 to 1 at `start`, so a window that
+/// starts at 1 still finishes at 1.
+pub fn remap_transition(transition: f64, start: f64, end: f64) -> f64 {
+    if transition >= end {
+        1.0
+    } else if transition <= start {
+        0.0
+    } else {
+        (transition - start) / (end - start)
+    }
+}
+
+/// The scale a value is blended on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterpolationSpace {
+    Linear,
+    /// Blend the logarithm, so each step multiplies by the same factor. Suits values that span
+    /// orders of magnitude, like the density of a collapsing cloud. Falls back to linear when
+    /// either end is zero or they differ in sign.
+    Logarithmic,
+    /// Blend the value raised to this exponent. `PowerLaw(1.0 / 3.0)` on a volume moves the
+    /// radius linearly; an exponent of zero is the logarithmic space. Falls back to linear when
+    /// the ends differ in sign, or when either is zero for a negative exponent.
+    PowerLaw(f64),
+}
+
+impl InterpolationSpace {
+    pub fn lerp(&self, value_a: f64, value_b: f64, transition: f64) -> f64 {
+        let same_sign = value_a * value_b > 0.0;
+        match *self {
+            InterpolationSpace::Logarithmic | InterpolationSpace::PowerLaw(0.0) if same_sign => {
+                value_a * (value_b / value_a).powf(transition)
+            }
+            InterpolationSpace::PowerLaw(exponent)
+                if same_sign || (exponent > 0.0 && value_a * value_b == 0.0) =>
+            {
+                // blend magnitudes, so a pair of negative values mirrors a pair of positive ones
+                let sign = if value_a + value_b < 0.0 { -1.0 } else { 1.0 };
+                let (a, b) = (value_a.abs().powf(exponent), value_b.abs().powf(exponent));
+                sign * (a + (b - a) * transition).powf(1.0 / exponent)
+            }
+            _ => value_a + (value_b - value_a) * transition,
+        }
+    }
+
+    pub fn lerp_quantity<D, U>(
+        &self,
+        value_a: Quantity<D, U, f64>,
+        value_b: Quantity<D, U, f64>,
+        transition: f64,
+    ) -> Quantity<D, U, f64>
+    where
+        D: Dimension + ?Sized,
+        U: Units<f64> + ?Sized,
+    {
+        Quantity {
+            dimension: PhantomData,
+            units: PhantomData,
+            value: self.lerp(value_a.value, value_b.value, transition),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Interpolationf64AsyncOptions(pub f64);
 
 impl Interpolatable for f64 {
@@ -43,7 +127,6 @@ impl Interpolatable for f64 {
         ease: Option<EasingFunction>,
         asynchronous_options: Option<Self::AO>,
     ) -> f64 {
-        println!();
         let transition_adjusted = if asynchronous_options.is_some() {
             Self::offset_transition(transition, asynchronous_options.unwrap().0)
         } else {
@@ -53,4 +136,303 @@ impl Interpolatable for f64 {
         let output: f64 = Self::lerp(*self, *other, transition_adjusted, ease);
         output
     }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> f64 {
+        let adjusted = match asynchronous_options {
+            Some(offset) => Self::offset_transition(transition, offset.0),
+            None => transition,
+        };
+        space.lerp(*self, *other, ease.map_or(adjusted, |ease| ease(adjusted)))
+    }
+}
+
+/// Quantities store their value in base units, so every unit of a dimension interpolates the
+/// same way as its raw value. Thermodynamic temperatures, which uom won't subtract, included.
+impl<D, U> Interpolatable for Quantity<D, U, f64>
+where
+    D: Dimension + ?Sized,
+    U: Units<f64> + ?Sized,
+{
+    type AO = Interpolationf64AsyncOptions;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        Quantity {
+            dimension: PhantomData,
+            units: PhantomData,
+            value: self.value.interpolate_in_space(
+                &other.value,
+                transition,
+                ease,
+                space,
+                asynchronous_options,
+            ),
+        }
+    }
+}
+
+/// Two values interpolate as usual. With only one side present there is nothing to blend, so
+/// the origin holds until the transition completes and then jumps to the target.
+impl<T: Interpolatable + Clone> Interpolatable for Option<T> {
+    type AO = T::AO;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        match (self, other) {
+            (Some(origin), Some(target)) => Some(origin.interpolate_in_space(
+                target,
+                transition,
+                ease,
+                space,
+                asynchronous_options,
+            )),
+            _ if transition >= 1.0 => other.clone(),
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Element-wise interpolation, with the same asynchronous options for every element.
+/// Where the vectors differ in length only the shared elements are blended, and the extra
+/// elements of the longer one are kept before the halfway point if it is the origin, or from
+/// the halfway point on if it is the target.
+impl<T> Interpolatable for Vec<T>
+where
+    T: Interpolatable + Clone,
+    T::AO: Clone,
+{
+    type AO = T::AO;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        let shared = self.len().min(other.len());
+        let tail = if transition < 0.5 { self } else { other };
+        self.iter()
+            .zip(other)
+            .map(|(origin, target)| {
+                origin.interpolate_in_space(
+                    target,
+                    transition,
+                    ease,
+                    space,
+                    asynchronous_options.clone(),
+                )
+            })
+            .chain(tail.iter().skip(shared).cloned())
+            .collect()
+    }
 }
+
+/// Element-wise interpolation, with the same asynchronous options for every element.
+impl<T, const N: usize> Interpolatable for [T; N]
+where
+    T: Interpolatable,
+    T::AO: Clone,
+{
+    type AO = T::AO;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        std::array::from_fn(|index| {
+            self[index].interpolate_in_space(
+                &other[index],
+                transition,
+                ease,
+                space,
+                asynchronous_options.clone(),
+            )
+        })
+    }
+}
+
+/// Keys missing from either side are treated as holding the default value, zero for numbers
+/// and quantities, so entries fade in and out just as molecules do in a `Composition`.
+impl<K, V> Interpolatable for HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Interpolatable + Default,
+    V::AO: Clone,
+{
+    type AO = V::AO;
+    fn interpolate(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        self.interpolate_in_space(
+            other,
+            transition,
+            ease,
+            InterpolationSpace::Linear,
+            asynchronous_options,
+        )
+    }
+
+    fn interpolate_in_space(
+        &self,
+        other: &Self,
+        transition: f64,
+        ease: Option<EasingFunction>,
+        space: InterpolationSpace,
+        asynchronous_options: Option<Self::AO>,
+    ) -> Self {
+        let missing = V::default();
+        self.keys()
+            .chain(other.keys().filter(|key| !self.contains_key(key)))
+            .map(|key| {
+                let origin = self.get(key).unwrap_or(&missing);
+                let target = other.get(key).unwrap_or(&missing);
+                let value = origin.interpolate_in_space(
+                    target,
+                    transition,
+                    ease,
+                    space,
+                    asynchronous_options.clone(),
+                );
+                (key.clone(), value)
+            })
+            .collect()
+    }
+}
+
+/// Each element of a tuple has its own type, so each takes its own asynchronous options.
+macro_rules! interpolatable_tuple {
+    ($($element:ident $index:tt),+) => {
+        impl<$($element: Interpolatable),+> Interpolatable for ($($element,)+) {
+            type AO = ($(Option<$element::AO>,)+);
+            fn interpolate(
+                &self,
+                other: &Self,
+                transition: f64,
+                ease: Option<EasingFunction>,
+                asynchronous_options: Option<Self::AO>,
+            ) -> Self {
+                self.interpolate_in_space(
+                    other,
+                    transition,
+                    ease,
+                    InterpolationSpace::Linear,
+                    asynchronous_options,
+                )
+            }
+
+            fn interpolate_in_space(
+                &self,
+                other: &Self,
+                transition: f64,
+                ease: Option<EasingFunction>,
+                space: InterpolationSpace,
+                asynchronous_options: Option<Self::AO>,
+            ) -> Self {
+                match asynchronous_options {
+                    Some(options) => ($(
+                        self.$index.interpolate_in_space(
+                            &other.$index, transition, ease, space, options.$index,
+                        ),
+                    )+),
+                    None => ($(
+                        self.$index.interpolate_in_space(&other.$index, transition, ease, space, None),
+                    )+),
+                }
+            }
+        }
+    };
+}
+
+interpolatable_tuple!(A 0);
+interpolatable_tuple!(A 0, B 1);
+interpolatable_tuple!(A 0, B 1, C 2);
+interpolatable_tuple!(A 0, B 1, C 2, D 3);
@@ -1,10 +1,23 @@
 #[cfg(test)]
 mod tests {
-    use crate::transition::transition::{Interpolatable, Interpolationf64AsyncOptions};
+    use crate::formulae::constants::GAS_CONSTANT;
+    use crate::gas::gas::{
+        ConstrainedGasOptions, GasInterpolationBasis, UniformGasAsynchronousOptions,
+    };
+    use crate::transition::easing::quad_in;
+    use crate::transition::transition::{
+        Interpolatable, InterpolationSpace, Interpolationf64AsyncOptions,
+    };
     use crate::{volume, Composition, Element, Molecule, SiliconIsotope, UniformGas};
-    use uom::si::f64::{Length, ThermodynamicTemperature};
-    use uom::si::length::light_year;
+    use float_cmp::assert_approx_eq;
+    use photon_derive::Interpolatable;
+    use std::collections::HashMap;
+    use uom::si::f64::{Length, Mass, MassDensity, ThermodynamicTemperature, Volume};
+    use uom::si::length::{kilometer, light_year};
+    use uom::si::mass::kilogram;
+    use uom::si::mass_density::kilogram_per_cubic_meter;
     use uom::si::thermodynamic_temperature::kelvin;
+    use uom::si::volume::cubic_meter;
 
     #[test]
     fn test_f64_interpolation() {
@@ -217,4 +230,455 @@ mod tests {
 
     #[test]
     fn interpolate_composition() {}
+
+    #[derive(Debug, Clone, PartialEq, Interpolatable)]
+    struct Probe {
+        altitude: Length,
+        #[interpolatable(ease = "quad_in")]
+        fuel: f64,
+        #[interpolatable(offset)]
+        temperature: ThermodynamicTemperature,
+        #[interpolatable(skip)]
+        name: String,
+    }
+
+    fn probe(altitude: f64, fuel: f64, temperature: f64, name: &str) -> Probe {
+        Probe {
+            altitude: Length::new::<kilometer>(altitude),
+            fuel,
+            temperature: ThermodynamicTemperature::new::<kelvin>(temperature),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn transition_derived_struct() {
+        let origin = probe(100.0, 10.0, 300.0, "origin");
+        let target = probe(300.0, 0.0, 100.0, "target");
+
+        assert_eq!(origin.interpolate(&target, 0.0, None, None), origin);
+        let end = origin.interpolate(&target, 1.0, None, None);
+        assert_eq!(end.altitude, target.altitude);
+        assert_eq!(end.name, "origin");
+
+        let mid = origin.interpolate(&target, 0.5, None, None);
+        assert_eq!(mid.altitude, Length::new::<kilometer>(200.0));
+        assert_eq!(
+            mid.temperature,
+            ThermodynamicTemperature::new::<kelvin>(200.0)
+        );
+        // the field's own easing wins over the one passed in
+        assert_eq!(mid.fuel, 7.5);
+        assert_eq!(
+            origin.interpolate(&target, 0.5, Some(|t| t), None).fuel,
+            7.5
+        );
+
+        let delayed = origin.interpolate(
+            &target,
+            0.5,
+            None,
+            Some(ProbeAsynchronousOptions {
+                temperature_offset: Some(Interpolationf64AsyncOptions(0.5)),
+            }),
+        );
+        assert_eq!(delayed.temperature, origin.temperature);
+        assert_eq!(delayed.altitude, mid.altitude);
+    }
+
+    #[test]
+    fn transition_gas_with_temperature_offset() {
+        let gas = |radius: f64, temperature: f64| {
+            UniformGas::composite_from_vacuum_properties(
+                volume::sphere_volume_from_length(Length::new::<light_year>(radius)),
+                300.0,
+                ThermodynamicTemperature::new::<kelvin>(temperature),
+                Composition(vec![(Molecule::molecular_hydrogen(), 100.0)]),
+            )
+        };
+        let origin = gas(100.0, 10.0);
+        let target = gas(50.0, 110.0);
+
+        let options = || UniformGasAsynchronousOptions {
+            temperature_offset: Some(Interpolationf64AsyncOptions(0.5)),
+            ..Default::default()
+        };
+        let early = origin.interpolate(&target, 0.25, None, Some(options()));
+        assert_eq!(early.temperature, origin.temperature);
+        assert_ne!(early.volume, origin.volume);
+
+        let late = origin.interpolate(&target, 0.75, Some(quad_in), Some(options()));
+        assert_eq!(
+            late.temperature,
+            ThermodynamicTemperature::new::<kelvin>(35.0)
+        );
+    }
+
+    #[test]
+    fn transition_quantities_in_any_unit() {
+        let origin = Length::new::<light_year>(1.0);
+        let target = Length::new::<kilometer>(0.0);
+        assert_eq!(
+            origin.interpolate(&target, 0.25, None, None),
+            Length::new::<light_year>(0.75)
+        );
+
+        let delayed = ThermodynamicTemperature::new::<kelvin>(10.0).interpolate(
+            &ThermodynamicTemperature::new::<kelvin>(20.0),
+            0.75,
+            None,
+            Some(Interpolationf64AsyncOptions(0.5)),
+        );
+        assert_eq!(delayed, ThermodynamicTemperature::new::<kelvin>(15.0));
+    }
+
+    #[test]
+    fn transition_options() {
+        assert_eq!(
+            Some(1.0).interpolate(&Some(3.0), 0.5, None, None),
+            Some(2.0)
+        );
+        assert_eq!(Some(1.0).interpolate(&None, 0.5, None, None), Some(1.0));
+        assert_eq!(Some(1.0).interpolate(&None, 1.0, None, None), None);
+        assert_eq!(None.interpolate(&Some(3.0), 0.99, None, None), None);
+        assert_eq!(None.interpolate(&Some(3.0), 1.0, None, None), Some(3.0));
+    }
+
+    #[test]
+    fn transition_sequences() {
+        let origin = vec![0.0, 10.0, 20.0];
+        let target = vec![10.0, 10.0, 0.0];
+        assert_eq!(
+            origin.interpolate(&target, 0.5, None, None),
+            vec![5.0, 10.0, 10.0]
+        );
+
+        let delayed = [0.0, 4.0].interpolate(
+            &[4.0, 0.0],
+            0.75,
+            None,
+            Some(Interpolationf64AsyncOptions(0.5)),
+        );
+        assert_eq!(delayed, [2.0, 2.0]);
+
+        let probes = vec![probe(0.0, 0.0, 0.0, "a"), probe(100.0, 0.0, 100.0, "b")];
+        let moved = vec![probe(100.0, 0.0, 0.0, "c"), probe(0.0, 0.0, 100.0, "d")];
+        let mid = probes.interpolate(&moved, 0.5, None, None);
+        assert_eq!(mid[0].altitude, mid[1].altitude);
+        assert_eq!(mid[1].name, "b");
+    }
+
+    #[test]
+    fn transition_sequences_of_different_lengths() {
+        let short = vec![0.0];
+        let long = vec![10.0, 20.0, 30.0];
+        assert_eq!(short.interpolate(&long, 0.0, None, None), vec![0.0]);
+        assert_eq!(short.interpolate(&long, 0.25, None, None), vec![2.5]);
+        assert_eq!(
+            short.interpolate(&long, 0.5, None, None),
+            vec![5.0, 20.0, 30.0]
+        );
+        assert_eq!(
+            long.interpolate(&short, 0.25, None, None),
+            vec![7.5, 20.0, 30.0]
+        );
+        assert_eq!(long.interpolate(&short, 0.5, None, None), vec![5.0]);
+        assert_eq!(long.interpolate(&short, 1.0, None, None), vec![0.0]);
+    }
+
+    #[test]
+    fn transition_tuples() {
+        let origin = (1.0, Mass::new::<kilogram>(2.0));
+        let target = (3.0, Mass::new::<kilogram>(4.0));
+        assert_eq!(
+            origin.interpolate(&target, 0.5, None, None),
+            (2.0, Mass::new::<kilogram>(3.0))
+        );
+
+        let delayed = origin.interpolate(
+            &target,
+            0.5,
+            None,
+            Some((None, Some(Interpolationf64AsyncOptions(0.5)))),
+        );
+        assert_eq!(delayed, (2.0, Mass::new::<kilogram>(2.0)));
+    }
+
+    #[test]
+    fn transition_maps_fill_missing_keys() {
+        let origin = HashMap::from([
+            ("iron", Mass::new::<kilogram>(4.0)),
+            ("ice", Mass::new::<kilogram>(2.0)),
+        ]);
+        let target = HashMap::from([
+            ("iron", Mass::new::<kilogram>(2.0)),
+            ("rock", Mass::new::<kilogram>(8.0)),
+        ]);
+
+        let mid = origin.interpolate(&target, 0.5, None, None);
+        assert_eq!(mid.len(), 3);
+        assert_eq!(mid["iron"], Mass::new::<kilogram>(3.0));
+        assert_eq!(mid["ice"], Mass::new::<kilogram>(1.0));
+        assert_eq!(mid["rock"], Mass::new::<kilogram>(4.0));
+
+        let end = origin.interpolate(&target, 1.0, None, None);
+        assert_eq!(end["ice"], Mass::new::<kilogram>(0.0));
+    }
+
+    fn collapsing_cloud() -> (UniformGas, UniformGas) {
+        let origin = UniformGas::composite_from_vacuum_properties(
+            volume::sphere_volume_from_length(Length::new::<light_year>(10.0)),
+            1e8,
+            ThermodynamicTemperature::new::<kelvin>(10.0),
+            Composition(vec![
+                (Molecule::molecular_hydrogen(), 90.0),
+                (Molecule::atomic_helium(), 10.0),
+            ]),
+        );
+        let target = UniformGas::composite_from_vacuum_properties(
+            volume::sphere_volume_from_length(Length::new::<light_year>(0.01)),
+            1e16,
+            ThermodynamicTemperature::new::<kelvin>(1000.0),
+            Composition(vec![(Molecule::molecular_hydrogen(), 100.0)]),
+        );
+        (origin, target)
+    }
+
+    fn assert_valid_gas(gas: &UniformGas) {
+        let ideal = gas.moles.value * GAS_CONSTANT * gas.temperature.value;
+        assert_approx_eq!(
+            f64,
+            gas.pressure.value * gas.volume.value / ideal,
+            1.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            (gas.density * gas.volume / gas.mass).value,
+            1.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn transition_gas_constrained() {
+        let (origin, target) = collapsing_cloud();
+        let options = ConstrainedGasOptions {
+            basis: GasInterpolationBasis::VolumeAndDensity,
+            volume: InterpolationSpace::Logarithmic,
+            mass: InterpolationSpace::Linear,
+            density: InterpolationSpace::Logarithmic,
+            temperature: InterpolationSpace::Linear,
+        };
+
+        for step in 0..=10 {
+            let transition = step as f64 / 10.0;
+            let gas = origin.interpolate_constrained(&target, transition, None, &options, None);
+            assert_valid_gas(&gas);
+        }
+
+        let start = origin.interpolate_constrained(&target, 0.0, None, &options, None);
+        assert_approx_eq!(f64, (start.moles / origin.moles).value, 1.0, epsilon = 1e-9);
+        assert_approx_eq!(
+            f64,
+            (start.pressure / origin.pressure).value,
+            1.0,
+            epsilon = 1e-9
+        );
+        let end = origin.interpolate_constrained(&target, 1.0, None, &options, None);
+        assert_approx_eq!(f64, (end.mass / target.mass).value, 1.0, epsilon = 1e-9);
+
+        // half way in log space is the geometric mean
+        let mid = origin.interpolate_constrained(&target, 0.5, None, &options, None);
+        assert_approx_eq!(
+            f64,
+            mid.volume.value,
+            (origin.volume.value * target.volume.value).sqrt(),
+            epsilon = mid.volume.value * 1e-9
+        );
+        // whereas independent blending breaks the ideal gas law
+        let unconstrained = origin.interpolate(&target, 0.5, None, None);
+        let ideal = unconstrained.moles.value * GAS_CONSTANT * unconstrained.temperature.value;
+        assert!(
+            (unconstrained.pressure.value * unconstrained.volume.value / ideal - 1.0).abs() > 0.1
+        );
+    }
+
+    #[test]
+    fn transition_gas_constrained_offsets() {
+        let (origin, target) = collapsing_cloud();
+        let options = ConstrainedGasOptions {
+            basis: GasInterpolationBasis::VolumeAndMass,
+            volume: InterpolationSpace::Linear,
+            mass: InterpolationSpace::Linear,
+            density: InterpolationSpace::Linear,
+            temperature: InterpolationSpace::Linear,
+        };
+        let offsets = UniformGasAsynchronousOptions {
+            volume_offset: Some(Interpolationf64AsyncOptions(0.5)),
+            materials_offset: Some(Interpolationf64AsyncOptions(0.5)),
+            ..Default::default()
+        };
+
+        let gas = origin.interpolate_constrained(&target, 0.25, None, &options, Some(offsets));
+        assert_valid_gas(&gas);
+        assert_eq!(gas.volume, origin.volume);
+        assert_ne!(gas.mass, origin.mass);
+        assert!(gas.materials.0.contains(&(Molecule::atomic_helium(), 10.0)));
+    }
+
+    #[test]
+    fn transition_spaces() {
+        let logarithmic = InterpolationSpace::Logarithmic;
+        assert_approx_eq!(f64, logarithmic.lerp(1.0, 1e10, 0.5), 1e5, epsilon = 1e-6);
+        assert_approx_eq!(
+            f64,
+            logarithmic.lerp(-1.0, -100.0, 0.5),
+            -10.0,
+            epsilon = 1e-9
+        );
+        // nothing to take the logarithm of, so blend linearly
+        assert_eq!(logarithmic.lerp(0.0, 10.0, 0.5), 5.0);
+        assert_eq!(logarithmic.lerp(-10.0, 10.0, 0.25), -5.0);
+
+        assert_eq!(InterpolationSpace::PowerLaw(1.0).lerp(2.0, 4.0, 0.5), 3.0);
+        assert_approx_eq!(
+            f64,
+            InterpolationSpace::PowerLaw(0.0).lerp(1.0, 100.0, 0.5),
+            10.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            InterpolationSpace::PowerLaw(0.5).lerp(0.0, 16.0, 0.5),
+            4.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            InterpolationSpace::PowerLaw(2.0).lerp(-3.0, -4.0, 1.0),
+            -4.0,
+            epsilon = 1e-9
+        );
+
+        // a cube-root power law on a volume moves its radius linearly
+        let volume = |radius: f64| Volume::new::<cubic_meter>(radius.powi(3));
+        let mid = volume(1.0).interpolate_in_space(
+            &volume(3.0),
+            0.5,
+            None,
+            InterpolationSpace::PowerLaw(1.0 / 3.0),
+            None,
+        );
+        assert_approx_eq!(f64, mid.value, 8.0, epsilon = 1e-9);
+
+        // offsets and easing still shape the transition
+        let delayed = 1.0.interpolate_in_space(
+            &100.0,
+            0.75,
+            Some(quad_in),
+            logarithmic,
+            Some(Interpolationf64AsyncOptions(0.5)),
+        );
+        assert_approx_eq!(f64, delayed, 100.0_f64.powf(0.25), epsilon = 1e-9);
+    }
+
+    #[derive(Debug, Clone, Interpolatable)]
+    struct Clump {
+        #[interpolatable(space = "logarithmic")]
+        density: MassDensity,
+        #[interpolatable(power_law = 0.5)]
+        area: f64,
+        temperature: f64,
+    }
+
+    #[test]
+    fn transition_spaces_per_field() {
+        let origin = Clump {
+            density: MassDensity::new::<kilogram_per_cubic_meter>(1e-20),
+            area: 1.0,
+            temperature: 10.0,
+        };
+        let target = Clump {
+            density: MassDensity::new::<kilogram_per_cubic_meter>(1e-10),
+            area: 9.0,
+            temperature: 1000.0,
+        };
+
+        let mid = origin.interpolate(&target, 0.5, None, None);
+        assert_approx_eq!(f64, mid.density.value / 1e-15, 1.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, mid.area, 4.0, epsilon = 1e-9);
+        assert_eq!(mid.temperature, 505.0);
+
+        // fields without a space of their own follow the caller's
+        let mid =
+            origin.interpolate_in_space(&target, 0.5, None, InterpolationSpace::Logarithmic, None);
+        assert_approx_eq!(f64, mid.temperature, 100.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, mid.area, 4.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn transition_gas_and_composition_in_log_space() {
+        let (origin, target) = collapsing_cloud();
+        let mid =
+            origin.interpolate_in_space(&target, 0.5, None, InterpolationSpace::Logarithmic, None);
+        assert_approx_eq!(
+            f64,
+            mid.density.value / (origin.density.value * target.density.value).sqrt(),
+            1.0,
+            epsilon = 1e-9
+        );
+
+        let c1 = Composition(vec![
+            (Molecule::molecular_hydrogen(), 99.0),
+            (Molecule::atomic_helium(), 1.0),
+        ]);
+        let c2 = Composition(vec![
+            (Molecule::molecular_hydrogen(), 1.0),
+            (Molecule::atomic_helium(), 99.0),
+        ]);
+        // both blend to √99, then are scaled back to a total of 100
+        let mid = c1.interpolate_in_space(&c2, 0.5, None, InterpolationSpace::Logarithmic, None);
+        assert_approx_eq!(
+            f64,
+            mid.fraction_of(&Molecule::atomic_helium()),
+            50.0,
+            epsilon = 1e-9
+        );
+        assert_eq!(mid.0[0].0, Molecule::molecular_hydrogen());
+        assert_eq!(mid.0[1].0, Molecule::atomic_helium());
+    }
+
+    #[test]
+    fn transition_composition_in_log_space_keeps_its_total() {
+        let c1 = Composition(vec![
+            (Molecule::molecular_hydrogen(), 90.0),
+            (Molecule::atomic_helium(), 10.0),
+        ]);
+        let c2 = Composition(vec![
+            (Molecule::molecular_hydrogen(), 50.0),
+            (Molecule::atomic_helium(), 50.0),
+        ]);
+        for transition in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            let blended = c1.interpolate_in_space(
+                &c2,
+                transition,
+                None,
+                InterpolationSpace::Logarithmic,
+                None,
+            );
+            assert_approx_eq!(f64, blended.total(), 100.0, epsilon = 1e-9);
+        }
+
+        // √(90 × 50) and √(10 × 50) are in the ratio 3 to 1
+        let mid = c1.interpolate_in_space(&c2, 0.5, None, InterpolationSpace::Logarithmic, None);
+        assert_approx_eq!(
+            f64,
+            mid.fraction_of(&Molecule::molecular_hydrogen()),
+            75.0,
+            epsilon = 1e-9
+        );
+        let again = c1.interpolate_in_space(&c2, 0.5, None, InterpolationSpace::Logarithmic, None);
+        assert_eq!(mid.0, again.0);
+    }
 }
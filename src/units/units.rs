@@ -8,6 +8,15 @@ pub mod length {
     }
 }
 
+pub mod angle {
+    unit! {
+        system: uom::si;
+        quantity: uom::si::angle;
+
+        @hour_angle: 2.617_993_877_991_494_4_E-1; "h", "hour_angle", "hour_angles";
+    }
+}
+
 pub mod mass {
     unit! {
         system: uom::si;
@@ -40,6 +49,15 @@ pub mod power {
     }
 }
 
+pub mod mass_rate {
+    unit! {
+        system: uom::si;
+        quantity: uom::si::mass_rate;
+
+        @solar_mass_per_year: 6.30707762557077E+22; "S∅/a", "solar_mass_per_year", "solar_masses_per_year";
+    }
+}
+
 pub mod time {
     unit! {
         system: uom::si;
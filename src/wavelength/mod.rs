@@ -1 +1,5 @@
+pub mod photometry;
+mod photometry_test;
+pub mod spectrum;
+mod spectrum_test;
 pub mod wavelength;
@@ -0,0 +1,192 @@
+use std::sync::OnceLock;
+
+use uom::si::f64::{Length, ThermodynamicTemperature};
+use uom::si::length::{meter, nanometer, parsec};
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::formulae::constants::C;
+use crate::wavelength::spectrum::{planck, Spectrum};
+
+/// The temperature of the black body used to stand in for Vega, which defines zero magnitude in
+/// the Vega system.
+const VEGA_TEMPERATURE: f64 = 9602.0;
+/// The flux density received from Vega at 550nm, in W·m⁻³
+const VEGA_FLUX_AT_550NM: f64 = 3.63E-2;
+/// The flux density that defines zero magnitude in the AB system, 3631 Jy, in W·m⁻²·Hz⁻¹
+const AB_ZERO_POINT: f64 = 3631.0E-26;
+/// How many standard deviations either side of the effective wavelength a band is integrated over
+const BAND_EXTENT: f64 = 4.0;
+const BAND_INTEGRATION_STEPS: usize = 400;
+
+/// The flux a magnitude of zero corresponds to in a band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotometricSystem {
+    /// Magnitudes relative to the star Vega, used by the Johnson-Cousins filters
+    Vega,
+    /// Magnitudes relative to a flat spectrum of 3631 Jy, used by the SDSS filters
+    AB,
+}
+
+/// A photometric filter, modelled as a gaussian transmission curve with the filter's effective
+/// wavelength and full width at half maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub name: &'static str,
+    pub system: PhotometricSystem,
+    effective_wavelength: f64,
+    width: f64,
+}
+
+impl Band {
+    pub const U: Band = Band::vega("U", 366.0, 65.0);
+    pub const B: Band = Band::vega("B", 438.0, 89.0);
+    pub const V: Band = Band::vega("V", 545.0, 84.0);
+    pub const R: Band = Band::vega("R", 641.0, 158.0);
+    pub const I: Band = Band::vega("I", 798.0, 154.0);
+
+    pub const SDSS_U: Band = Band::ab("u", 355.1, 59.9);
+    pub const SDSS_G: Band = Band::ab("g", 468.6, 137.9);
+    pub const SDSS_R: Band = Band::ab("r", 616.5, 137.3);
+    pub const SDSS_I: Band = Band::ab("i", 748.1, 152.3);
+    pub const SDSS_Z: Band = Band::ab("z", 893.1, 95.0);
+
+    /// Johnson-Cousins UBVRI, with widths from Bessell (2005)
+    pub const JOHNSON_COUSINS: [Band; 5] = [Band::U, Band::B, Band::V, Band::R, Band::I];
+    /// Sloan Digital Sky Survey ugriz
+    pub const SDSS: [Band; 5] = [
+        Band::SDSS_U,
+        Band::SDSS_G,
+        Band::SDSS_R,
+        Band::SDSS_I,
+        Band::SDSS_Z,
+    ];
+
+    const fn vega(name: &'static str, effective_wavelength: f64, width: f64) -> Band {
+        Band {
+            name,
+            system: PhotometricSystem::Vega,
+            effective_wavelength,
+            width,
+        }
+    }
+
+    const fn ab(name: &'static str, effective_wavelength: f64, width: f64) -> Band {
+        Band {
+            name,
+            system: PhotometricSystem::AB,
+            effective_wavelength,
+            width,
+        }
+    }
+
+    pub fn effective_wavelength(&self) -> Length {
+        Length::new::<nanometer>(self.effective_wavelength)
+    }
+
+    /// The full width of the band at half of its peak transmission
+    pub fn width(&self) -> Length {
+        Length::new::<nanometer>(self.width)
+    }
+
+    /// The fraction of light the filter lets through at a given wavelength
+    pub fn transmission(&self, wavelength: Length) -> f64 {
+        let sigma = self.width() / (8.0 * f64::ln(2.0)).sqrt();
+        let offset = (wavelength - self.effective_wavelength()).value / sigma.value;
+        (-0.5 * offset.powi(2)).exp()
+    }
+
+    /// The photon-weighted flux through the band, `∫ F(λ) T(λ) λ dλ`, of a flux density given as
+    /// a function of wavelength
+    fn integrate(&self, flux: impl Fn(Length) -> f64) -> f64 {
+        let sigma = self.width() / (8.0 * f64::ln(2.0)).sqrt();
+        let from = self.effective_wavelength() - sigma * BAND_EXTENT;
+        let step = sigma * (2.0 * BAND_EXTENT / BAND_INTEGRATION_STEPS as f64);
+        let integrand = |wavelength: Length| {
+            flux(wavelength) * self.transmission(wavelength) * wavelength.value
+        };
+        (0..BAND_INTEGRATION_STEPS).fold(0.0, |acc, index| {
+            let a = from + step * index as f64;
+            let b = a + step;
+            acc + step.value * (integrand(a) + integrand(b)) / 2.0
+        })
+    }
+
+    /// The flux density that defines zero magnitude at a given wavelength, in W·m⁻³
+    fn zero_point(&self, wavelength: Length) -> f64 {
+        match self.system {
+            PhotometricSystem::Vega => {
+                let vega = ThermodynamicTemperature::new::<kelvin>(VEGA_TEMPERATURE);
+                VEGA_FLUX_AT_550NM * planck(wavelength, vega)
+                    / planck(Length::new::<nanometer>(550.0), vega)
+            }
+            PhotometricSystem::AB => AB_ZERO_POINT * C / wavelength.value.powi(2),
+        }
+    }
+
+    /// The synthetic apparent magnitude of a flux density spectrum (W·m⁻³) seen through this band
+    pub fn magnitude(&self, flux: &Spectrum) -> f64 {
+        self.magnitude_of(|wavelength| flux.value_at(wavelength))
+    }
+
+    fn magnitude_of(&self, flux: impl Fn(Length) -> f64) -> f64 {
+        -2.5 * f64::log10(self.integrate(flux) / self.zero_point_flux())
+    }
+
+    /// The flux through the band of a zero magnitude source. Only depends on the band, so the
+    /// integrals for the standard bands are worked out once and shared.
+    fn zero_point_flux(&self) -> f64 {
+        static ZERO_POINT_FLUXES: OnceLock<Vec<(Band, f64)>> = OnceLock::new();
+        let integrate = |band: &Band| band.integrate(|wavelength| band.zero_point(wavelength));
+        ZERO_POINT_FLUXES
+            .get_or_init(|| {
+                Band::JOHNSON_COUSINS
+                    .iter()
+                    .chain(Band::SDSS.iter())
+                    .map(|band| (*band, integrate(band)))
+                    .collect()
+            })
+            .iter()
+            .find(|(band, _)| band == self)
+            .map_or_else(|| integrate(self), |(_, flux)| *flux)
+    }
+}
+
+/// The flux density (W·m⁻³) received at a given wavelength from a spherical black body of the
+/// given radius at the given distance, `F_λ = π B_λ(T) (R / d)²`
+pub fn blackbody_flux(
+    wavelength: Length,
+    temperature: ThermodynamicTemperature,
+    radius: Length,
+    distance: Length,
+) -> f64 {
+    std::f64::consts::PI * planck(wavelength, temperature) * (radius.value / distance.value).powi(2)
+}
+
+/// The synthetic apparent magnitude of a spherical black body seen through a band
+pub fn blackbody_magnitude(
+    temperature: ThermodynamicTemperature,
+    radius: Length,
+    distance: Length,
+    band: &Band,
+) -> f64 {
+    band.magnitude_of(|wavelength| blackbody_flux(wavelength, temperature, radius, distance))
+}
+
+/// The synthetic absolute magnitude of a spherical black body seen through a band, i.e. its
+/// apparent magnitude from ten parsecs away
+pub fn absolute_magnitude(
+    temperature: ThermodynamicTemperature,
+    radius: Length,
+    band: &Band,
+) -> f64 {
+    blackbody_magnitude(temperature, radius, Length::new::<parsec>(10.0), band)
+}
+
+/// The difference in magnitude of a black body between two bands, e.g. `B - V`.
+/// Colour indices do not depend on the size of or distance to the body.
+pub fn colour_index(temperature: ThermodynamicTemperature, a: &Band, b: &Band) -> f64 {
+    let radius = Length::new::<meter>(1.0);
+    let distance = Length::new::<meter>(1.0);
+    blackbody_magnitude(temperature, radius, distance, a)
+        - blackbody_magnitude(temperature, radius, distance, b)
+}
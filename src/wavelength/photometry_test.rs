@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::formulae::formulae::calculate_bv_index;
+    use crate::units::units::length::solar_radius;
+    use crate::wavelength::photometry::{
+        absolute_magnitude, blackbody_flux, colour_index, Band, PhotometricSystem,
+    };
+    use crate::wavelength::spectrum::Spectrum;
+    use float_cmp::assert_approx_eq;
+    use uom::si::f64::{Length, ThermodynamicTemperature};
+    use uom::si::length::{nanometer, parsec};
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    #[test]
+    fn band_transmission() {
+        assert_eq!(Band::V.transmission(Band::V.effective_wavelength()), 1.0);
+        assert_approx_eq!(
+            f64,
+            Band::V.transmission(Band::V.effective_wavelength() + Band::V.width() / 2.0),
+            0.5,
+            ulps = 4
+        );
+        assert!(Band::V.transmission(Length::new::<nanometer>(300.0)) < 1e-6);
+        assert!(Band::JOHNSON_COUSINS
+            .iter()
+            .all(|band| band.system == PhotometricSystem::Vega));
+        assert!(Band::SDSS
+            .iter()
+            .all(|band| band.system == PhotometricSystem::AB));
+    }
+
+    #[test]
+    fn vega_like_star_has_no_colour() {
+        let vega = ThermodynamicTemperature::new::<kelvin>(9602.0);
+        assert_approx_eq!(
+            f64,
+            colour_index(vega, &Band::B, &Band::V),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            colour_index(vega, &Band::U, &Band::I),
+            0.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn bv_index_of_stars() {
+        let sun = calculate_bv_index(ThermodynamicTemperature::new::<kelvin>(5778.0));
+        // the real sun is redder, at 0.65, as metal lines absorb more blue light than red
+        assert!(sun > 0.4 && sun < 0.7);
+
+        let hot = calculate_bv_index(ThermodynamicTemperature::new::<kelvin>(30000.0));
+        assert!(hot < -0.2);
+
+        let cool = calculate_bv_index(ThermodynamicTemperature::new::<kelvin>(3000.0));
+        assert!(cool > 1.3);
+    }
+
+    #[test]
+    fn absolute_magnitude_of_sun() {
+        // the sun has an absolute V magnitude of 4.83
+        let sun = absolute_magnitude(
+            ThermodynamicTemperature::new::<kelvin>(5778.0),
+            Length::new::<solar_radius>(1.0),
+            &Band::V,
+        );
+        assert!(sun > 4.6 && sun < 5.0);
+
+        // and an absolute g magnitude of around 5.1
+        let sun_g = absolute_magnitude(
+            ThermodynamicTemperature::new::<kelvin>(5778.0),
+            Length::new::<solar_radius>(1.0),
+            &Band::SDSS_G,
+        );
+        assert!(sun_g > 4.9 && sun_g < 5.4);
+    }
+
+    #[test]
+    fn magnitude_of_sampled_spectrum_matches_blackbody() {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(5778.0);
+        let radius = Length::new::<solar_radius>(1.0);
+        let distance = Length::new::<parsec>(10.0);
+        let spectrum = Spectrum::blackbody(
+            temperature,
+            Length::new::<nanometer>(200.0),
+            Length::new::<nanometer>(1200.0),
+            2001,
+        );
+        let flux = spectrum.scale(
+            blackbody_flux(
+                Length::new::<nanometer>(550.0),
+                temperature,
+                radius,
+                distance,
+            ) / spectrum.value_at(Length::new::<nanometer>(550.0)),
+        );
+        assert_approx_eq!(
+            f64,
+            Band::V.magnitude(&flux),
+            absolute_magnitude(temperature, radius, &Band::V),
+            epsilon = 1e-3
+        );
+    }
+}
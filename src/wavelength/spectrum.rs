@@ -0,0 +1,95 @@
+use uom::si::f64::{Length, ThermodynamicTemperature};
+
+use crate::formulae::constants::{BOLTZMANN_CONSTANT, C, PLANCK_CONSTANT};
+
+/// The spectral radiance of a black body at a given wavelength, from Planck's law
+/// `B_λ(T) = 2hc² / λ⁵ / (exp(hc / λkT) - 1)`, in W·sr⁻¹·m⁻³.
+pub fn planck(wavelength: Length, temperature: ThermodynamicTemperature) -> f64 {
+    let lambda = wavelength.value;
+    let exponent = (PLANCK_CONSTANT * C) / (lambda * BOLTZMANN_CONSTANT * temperature.value);
+    (2.0 * PLANCK_CONSTANT * C.powi(2)) / lambda.powi(5) / exponent.exp_m1()
+}
+
+/// A spectrum sampled at ascending wavelengths.
+/// Values are spectral densities per unit wavelength in SI units, either a radiance
+/// (W·sr⁻¹·m⁻³) or a flux density (W·m⁻³) depending on how the spectrum was built.
+#[derive(Debug, Clone)]
+pub struct Spectrum {
+    pub wavelengths: Vec<Length>,
+    pub values: Vec<f64>,
+}
+
+impl Spectrum {
+    /// Sample the Planck spectrum of a black body at `samples` evenly spaced wavelengths between
+    /// `from` and `to` inclusive.
+    pub fn blackbody(
+        temperature: ThermodynamicTemperature,
+        from: Length,
+        to: Length,
+        samples: usize,
+    ) -> Spectrum {
+        let step = (to - from) / (samples.max(2) - 1) as f64;
+        let wavelengths: Vec<Length> = (0..samples.max(2))
+            .map(|sample| from + step * sample as f64)
+            .collect();
+        let values = wavelengths
+            .iter()
+            .map(|wavelength| planck(*wavelength, temperature))
+            .collect();
+        Spectrum {
+            wavelengths,
+            values,
+        }
+    }
+
+    /// Multiply every sample by a constant, for example to turn a radiance into the flux
+    /// received from a star of a given size and distance.
+    pub fn scale(&self, factor: f64) -> Spectrum {
+        Spectrum {
+            wavelengths: self.wavelengths.clone(),
+            values: self.values.iter().map(|value| value * factor).collect(),
+        }
+    }
+
+    /// The value of the spectrum at an arbitrary wavelength by linear interpolation between
+    /// samples. The spectrum is zero outside the sampled range.
+    pub fn value_at(&self, wavelength: Length) -> f64 {
+        let upper = self
+            .wavelengths
+            .partition_point(|sample| *sample < wavelength);
+        if upper == 0 {
+            return match self.wavelengths.first() {
+                Some(first) if *first == wavelength => self.values[0],
+                _ => 0.0,
+            };
+        }
+        if upper >= self.wavelengths.len() {
+            return 0.0;
+        }
+        let (l0, l1) = (
+            self.wavelengths[upper - 1].value,
+            self.wavelengths[upper].value,
+        );
+        let (v0, v1) = (self.values[upper - 1], self.values[upper]);
+        v0 + (v1 - v0) * (wavelength.value - l0) / (l1 - l0)
+    }
+
+    /// Integrate the spectrum over wavelength with the trapezium rule
+    pub fn integrate(&self) -> f64 {
+        self.wavelengths
+            .windows(2)
+            .zip(self.values.windows(2))
+            .fold(0.0, |acc, (wavelengths, values)| {
+                acc + (wavelengths[1] - wavelengths[0]).value * (values[0] + values[1]) / 2.0
+            })
+    }
+
+    /// The sampled wavelength with the greatest value
+    pub fn peak_wavelength(&self) -> Option<Length> {
+        self.values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| self.wavelengths[index])
+    }
+}
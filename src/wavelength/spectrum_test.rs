@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::formulae::constants::STEFAN_BOLTZMANN_CONSTANT;
+    use crate::formulae::formulae::wavelength;
+    use crate::wavelength::spectrum::{planck, Spectrum};
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::PI;
+    use uom::si::f64::{Length, ThermodynamicTemperature};
+    use uom::si::length::{micrometer, nanometer};
+    use uom::si::thermodynamic_temperature::kelvin;
+
+    #[test]
+    fn planck_radiance_of_sun() {
+        // the sun's radiance peaks at around 2.6e13 W·sr⁻¹·m⁻³ near 500nm
+        let radiance = planck(
+            Length::new::<nanometer>(500.0),
+            ThermodynamicTemperature::new::<kelvin>(5778.0),
+        );
+        assert!(radiance > 2.5e13 && radiance < 2.7e13);
+    }
+
+    #[test]
+    fn blackbody_spectrum_obeys_stefan_boltzmann() {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(5778.0);
+        let spectrum = Spectrum::blackbody(
+            temperature,
+            Length::new::<nanometer>(10.0),
+            Length::new::<micrometer>(100.0),
+            100000,
+        );
+
+        // π ∫ B_λ dλ is the flux leaving the surface, σT⁴. The tolerance allows for the rounded
+        // Boltzmann constant.
+        assert_approx_eq!(
+            f64,
+            PI * spectrum.integrate() / (STEFAN_BOLTZMANN_CONSTANT * temperature.value.powi(4)),
+            1.0,
+            epsilon = 2e-3
+        );
+    }
+
+    #[test]
+    fn blackbody_spectrum_peaks_at_wiens_wavelength() {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(5778.0);
+        let spectrum = Spectrum::blackbody(
+            temperature,
+            Length::new::<nanometer>(100.0),
+            Length::new::<nanometer>(2000.0),
+            1901,
+        );
+        let peak = spectrum.peak_wavelength().unwrap().get::<nanometer>();
+        let wien = wavelength::from_temperature(temperature)
+            .peak_wavelength
+            .get::<nanometer>();
+        assert!((peak - wien).abs() <= 1.0);
+    }
+
+    #[test]
+    fn spectrum_sampling() {
+        let spectrum = Spectrum::blackbody(
+            ThermodynamicTemperature::new::<kelvin>(3000.0),
+            Length::new::<nanometer>(400.0),
+            Length::new::<nanometer>(500.0),
+            3,
+        );
+        assert_eq!(spectrum.wavelengths.len(), 3);
+        assert_eq!(
+            spectrum.value_at(Length::new::<nanometer>(400.0)),
+            spectrum.values[0]
+        );
+        assert_eq!(spectrum.value_at(Length::new::<nanometer>(300.0)), 0.0);
+        assert_eq!(spectrum.value_at(Length::new::<nanometer>(600.0)), 0.0);
+        assert_approx_eq!(
+            f64,
+            spectrum.value_at(Length::new::<nanometer>(425.0)),
+            (spectrum.values[0] + spectrum.values[1]) / 2.0,
+            ulps = 4
+        );
+        assert_eq!(spectrum.scale(2.0).values[2], spectrum.values[2] * 2.0);
+    }
+}